@@ -1,39 +1,60 @@
-macro_rules! generate_bench {
-    ($year:ident, $($day:ident: $expected:expr,)+) => {
-        extern crate criterion;
-        use criterion::{black_box, criterion_group, criterion_main, Criterion};
+//! Benches every registered solution, with one criterion group per year, and one benchmark
+//! group per day where parsing and each part are measured separately, variants side by side.
+//! Benches can be filtered by solution name: `cargo bench -- y2020::d11`
 
-        $(
-            fn $day(c: &mut Criterion) {
-                let input = adventofcode_rust::utils::Input::open(
-                    stringify!($year),
-                    stringify!($day)
-                ).unwrap();
-                let run = adventofcode_rust::$year::$day::run;
+extern crate criterion;
+use adventofcode_rust::registry;
+use criterion::{black_box, criterion_main, BenchmarkId, Criterion};
+use std::time::Duration;
 
-                assert_eq!(
-                    $expected,
-                    run(&input).expect("run failure")
-                );
-                c.bench_function(
-                    stringify!($day),
-                    |b| b.iter(|| run(black_box(&input)))
-                );
-            }
-        )*
+/// Runs one criterion group per year found in the registry
+fn years() {
+    let mut years: Vec<u16> = registry::solutions().iter().map(|s| s.year()).collect();
+    years.dedup();
+    for year in years {
+        let mut c = Criterion::default()
+            .warm_up_time(Duration::from_secs(2))
+            .measurement_time(Duration::from_secs(3))
+            .configure_from_args();
+        bench_year(&mut c, year);
+    }
+}
 
-        criterion_group!{
-            name = $year;
-            config = Criterion::default()
-                .warm_up_time(std::time::Duration::from_secs(2))
-                .measurement_time(std::time::Duration::from_secs(3));
-            targets = $($day,)*
+fn bench_year(c: &mut Criterion, year: u16) {
+    for solution in registry::solutions().iter().filter(|s| s.year() == year) {
+        if solution.variant().is_none() {
+            bench_day(c, year, solution.day());
         }
-        criterion_main!($year);
     }
 }
 
-generate_bench! {
-y2021,
-    d16: (984, 1015320896946),
+/// Benches all implementations of a day, checking first that variants give the answers of
+/// the main implementation
+fn bench_day(c: &mut Criterion, year: u16, day: u8) {
+    let variants = registry::variants(year, day);
+    let input = variants[0].input().unwrap();
+    let expected = variants[0].run(&input).expect("run failure");
+    let mut group = c.benchmark_group(variants[0].name());
+    for solution in &variants {
+        let variant = solution.variant().unwrap_or("main");
+        assert_eq!(
+            expected,
+            solution.run(&input).expect("run failure"),
+            "{} answers differ from the main implementation",
+            solution.name()
+        );
+        let parsed = solution.parse(&input).expect("parse failure");
+        group.bench_function(BenchmarkId::new("parse", variant), |b| {
+            b.iter(|| solution.parse(black_box(&input)))
+        });
+        group.bench_function(BenchmarkId::new("part1", variant), |b| {
+            b.iter(|| solution.part1(black_box(parsed.as_ref())))
+        });
+        group.bench_function(BenchmarkId::new("part2", variant), |b| {
+            b.iter(|| solution.part2(black_box(parsed.as_ref())))
+        });
+    }
+    group.finish();
 }
+
+criterion_main!(years);
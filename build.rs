@@ -0,0 +1,54 @@
+//! Generates one test per example file in `input/examples/yYYYY/dDD/`, included in the year
+//! module by `register_solutions!`. Every year in `src/` gets a file, even without examples.
+
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=input/examples");
+    println!("cargo:rerun-if-changed=src");
+
+    let out_dir = std::env::var("OUT_DIR").expect("OUT_DIR not set");
+    for year in sorted_entries("src", |name| name.starts_with('y')) {
+        let mut tests = String::new();
+        let year_dir = Path::new("input/examples").join(&year);
+        for day in sorted_entries(&year_dir, |name| name.starts_with('d')) {
+            let files = sorted_entries(year_dir.join(&day), |name| name.ends_with(".txt"));
+            for name in files.iter().map(|f| f.trim_end_matches(".txt")) {
+                if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                    panic!("invalid example name {}/{}/{}", year, day, name);
+                }
+                let year_number = &year[1..];
+                let day_number = day[1..].trim_start_matches('0');
+                writeln!(
+                    tests,
+                    r#"
+#[test]
+fn {day}_example_{name}() -> anyhow::Result<()> {{
+    let _ = pretty_env_logger::try_init();
+    let solutions = crate::registry::variants({year_number}, {day_number});
+    crate::utils::Example::open("{year}", "{day}", "{name}")?.check_all(&solutions)
+}}"#
+                )
+                .unwrap();
+            }
+        }
+        let path = Path::new(&out_dir).join(format!("examples_{}.rs", year));
+        fs::write(path, tests).expect("cannot write generated tests");
+    }
+}
+
+/// Names of the entries of a directory matching a filter, empty if it does not exist
+fn sorted_entries<P: AsRef<Path>>(dir: P, filter: fn(&str) -> bool) -> Vec<String> {
+    let mut names: Vec<String> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok()?.file_name().into_string().ok())
+            .filter(|name| filter(name))
+            .collect(),
+        Err(_) => vec![],
+    };
+    names.sort();
+    names
+}
@@ -0,0 +1,351 @@
+use adventofcode_rust::client::{session_token, Client};
+use adventofcode_rust::registry;
+use adventofcode_rust::utils::{
+    Answer, Answers, DayTiming, Input, Solution, Timings, Verdict, DEFAULT_PROFILE,
+};
+use anyhow::{bail, Result};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Runs the Advent of Code solutions
+#[derive(Parser)]
+#[command(name = "aoc")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Lists the registered solutions
+    List,
+    /// Runs solutions and prints their answers, all of them if no year is given
+    Run {
+        /// Puzzle year, for example 2020
+        year: Option<u16>,
+        /// Puzzle day, runs all days of the year if omitted
+        day: Option<u8>,
+        /// Name of an alternative implementation, for example `opt`
+        #[arg(long)]
+        variant: Option<String>,
+        /// Only print the answer to this part
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Input profile, the directory of `input/` to read inputs from
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+        /// Read the input from this file instead of `input/`, `-` for stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Print the results as JSON lines
+        #[arg(long)]
+        json: bool,
+    },
+    /// Checks answers against `input/<profile>/yYYYY/answers.toml`, all days if no year is given
+    Verify {
+        /// Puzzle year, for example 2020
+        year: Option<u16>,
+        /// Puzzle day, checks all days of the year if omitted
+        day: Option<u8>,
+        /// Name of an alternative implementation, for example `opt`
+        #[arg(long)]
+        variant: Option<String>,
+        /// Input profile, days without an input in this profile are skipped
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+        /// Store the computed answers of unknown parts into the answers file
+        #[arg(long)]
+        record: bool,
+    },
+    /// Times every step of each solution, including variants, and compares with a baseline
+    Time {
+        /// Puzzle year, for example 2020
+        year: Option<u16>,
+        /// Puzzle day, times all days of the year if omitted
+        day: Option<u8>,
+        /// Baseline timings, in JSON
+        #[arg(long, default_value = "timings.json")]
+        baseline: PathBuf,
+        /// Store the new timings in the baseline, keeping the days not measured
+        #[arg(long)]
+        save: bool,
+        /// Flag days slower than the baseline by more than this ratio
+        #[arg(long, default_value_t = 1.5)]
+        ratio: f64,
+    },
+    /// Downloads the input of a day, and optionally its description, into the input profile.
+    /// The session token is read from `AOC_SESSION` or the `.session` file.
+    Fetch {
+        /// Puzzle year, for example 2020
+        year: u16,
+        /// Puzzle day
+        day: u8,
+        /// Also download the puzzle description, again if it is already cached
+        #[arg(long)]
+        description: bool,
+        /// Input profile to store the files in
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+    },
+    /// Submits the answer to a part, computed by the solution if not given
+    Submit {
+        /// Puzzle year, for example 2020
+        year: u16,
+        /// Puzzle day
+        day: u8,
+        /// Puzzle part
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Answer to submit
+        answer: Option<String>,
+        /// Input profile of the account to submit for
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+    },
+}
+
+/// Answers and timing for one run
+#[derive(Serialize)]
+struct Report {
+    name: String,
+    year: u16,
+    day: u8,
+    variant: Option<&'static str>,
+    part1: Option<String>,
+    part2: Option<String>,
+    micros: u128,
+}
+
+impl Report {
+    fn print(&self, json: bool) -> Result<()> {
+        if json {
+            println!("{}", serde_json::to_string(self)?);
+            return Ok(());
+        }
+        let mut line = format!("{:<16}", self.name);
+        if let Some(answer) = &self.part1 {
+            line += &format!(" part 1: {:<16}", answer);
+        }
+        if let Some(answer) = &self.part2 {
+            line += &format!(" part 2: {:<16}", answer);
+        }
+        println!(
+            "{} {:>10.3?}",
+            line,
+            Duration::from_micros(self.micros as u64)
+        );
+        Ok(())
+    }
+}
+
+fn main() -> Result<()> {
+    let _ = pretty_env_logger::try_init();
+    match Cli::parse().command {
+        Command::List => {
+            for solution in registry::solutions() {
+                let (first, second) = solution.answer_types();
+                println!("{:<16} ({}, {})", solution.name(), first, second);
+            }
+            Ok(())
+        }
+        Command::Run {
+            year,
+            day,
+            variant,
+            part,
+            profile,
+            input,
+            json,
+        } => {
+            let solutions = select(year, day, variant.as_deref())?;
+            if input.is_some() && solutions.len() != 1 {
+                bail!("an input file can only be given when running a single day")
+            }
+            for solution in solutions {
+                let input = match &input {
+                    None => solution.input_from(&profile)?,
+                    Some(path) if path.as_os_str() == "-" => {
+                        Input::read_from(std::io::stdin())?.with_day(&solution.name())
+                    }
+                    Some(path) => Input::read_file(path)?.with_day(&solution.name()),
+                };
+                run(solution.as_ref(), &input, part)?.print(json)?;
+            }
+            Ok(())
+        }
+        Command::Verify {
+            year,
+            day,
+            variant,
+            profile,
+            record,
+        } => {
+            let mut failures = 0;
+            for solution in select(year, day, variant.as_deref())? {
+                if !solution.input_path(&profile).exists() {
+                    continue;
+                }
+                let output = solution.run(&solution.input_from(&profile)?)?;
+                let mut answers = Answers::open_for(&profile, solution.as_ref())?;
+                let verdicts = answers.verify(solution.day(), &output);
+                println!(
+                    "{:<16} part 1: {:<16} part 2: {}",
+                    solution.name(),
+                    verdicts.0.to_string(),
+                    verdicts.1
+                );
+                for verdict in [verdicts.0, verdicts.1] {
+                    if let Verdict::Fail { .. } = verdict {
+                        failures += 1;
+                    }
+                }
+                if record && answers.record(solution.day(), &output) {
+                    answers.save()?;
+                }
+            }
+            if failures > 0 {
+                bail!("{} wrong answers", failures)
+            }
+            Ok(())
+        }
+        Command::Time {
+            year,
+            day,
+            baseline,
+            save,
+            ratio,
+        } => time(year, day, &baseline, save, ratio),
+        Command::Fetch {
+            year,
+            day,
+            description,
+            profile,
+        } => {
+            let client = Client::new(session_token()?, &profile);
+            client.input(year, day)?;
+            if description {
+                println!("{}", client.description(year, day, true)?);
+            }
+            Ok(())
+        }
+        Command::Submit {
+            year,
+            day,
+            part,
+            answer,
+            profile,
+        } => {
+            let client = Client::new(session_token()?, &profile);
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let solution = match registry::find(year, day, None) {
+                        Some(solution) => solution,
+                        None => bail!("no solution for {} day {}", year, day),
+                    };
+                    let parsed = solution.parse(&client.input(year, day)?)?;
+                    let answer = match part {
+                        1 => solution.part1(parsed.as_ref())?,
+                        _ => solution.part2(parsed.as_ref())?,
+                    };
+                    if answer == Answer::Unsolved {
+                        bail!("part {} is not solved yet", part)
+                    }
+                    answer.to_string()
+                }
+            };
+            println!("{}: {:?}", answer, client.submit(year, day, part, &answer)?);
+            Ok(())
+        }
+    }
+}
+
+/// Selects the solutions to run, variants are only included when explicitly requested
+fn select(
+    year: Option<u16>,
+    day: Option<u8>,
+    variant: Option<&str>,
+) -> Result<Vec<Box<dyn Solution>>> {
+    let solutions: Vec<Box<dyn Solution>> = registry::solutions()
+        .into_iter()
+        .filter(|s| year.is_none_or(|year| s.year() == year))
+        .filter(|s| day.is_none_or(|day| s.day() == day))
+        .filter(|s| s.variant() == variant)
+        .collect();
+    if solutions.is_empty() {
+        bail!("no matching solution found")
+    }
+    Ok(solutions)
+}
+
+/// Parses the input once and only computes the requested parts
+fn run(solution: &dyn Solution, input: &Input, part: Option<u8>) -> Result<Report> {
+    let start = Instant::now();
+    let parsed = solution.parse(input)?;
+    let part1 = match part {
+        Some(2) => None,
+        _ => Some(solution.part1(parsed.as_ref())?.to_string()),
+    };
+    let part2 = match part {
+        Some(1) => None,
+        _ => Some(solution.part2(parsed.as_ref())?.to_string()),
+    };
+    let elapsed = start.elapsed();
+    Ok(Report {
+        name: solution.name(),
+        year: solution.year(),
+        day: solution.day(),
+        variant: solution.variant(),
+        part1,
+        part2,
+        micros: elapsed.as_micros(),
+    })
+}
+
+/// Prints the timings of the selected solutions, slowest first, and fails on regressions
+fn time(year: Option<u16>, day: Option<u8>, path: &Path, save: bool, ratio: f64) -> Result<()> {
+    let mut timings = Timings::default();
+    for solution in registry::solutions()
+        .into_iter()
+        .filter(|s| year.is_none_or(|year| s.year() == year))
+        .filter(|s| day.is_none_or(|day| s.day() == day))
+    {
+        let timing = DayTiming::measure(solution.as_ref(), &solution.input()?)?;
+        timings.insert(solution.name(), timing);
+    }
+    if timings.slowest_first().is_empty() {
+        bail!("no matching solution found")
+    }
+
+    let baseline = Timings::open(path)?.unwrap_or_default();
+    let mut regressions = 0;
+    println!(
+        "{:<16} {:>10} {:>10} {:>10} {:>10}",
+        "day", "parse", "part 1", "part 2", "total"
+    );
+    for (name, timing) in timings.slowest_first() {
+        let micros = |m| format!("{:.3?}", Duration::from_micros(m));
+        let mut line = format!(
+            "{:<16} {:>10} {:>10} {:>10} {:>10.3?}",
+            name,
+            micros(timing.parse),
+            micros(timing.part1),
+            micros(timing.part2),
+            timing.total()
+        );
+        if let Some(slowdown) = timings.regression(name, &baseline, ratio) {
+            line += &format!("  SLOWER x{:.1}", slowdown);
+            regressions += 1;
+        }
+        println!("{}", line);
+    }
+    if save {
+        timings.update(path)?;
+    }
+    if regressions > 0 {
+        bail!("{} days slower than the baseline", regressions)
+    }
+    Ok(())
+}
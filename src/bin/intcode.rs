@@ -0,0 +1,232 @@
+use adventofcode_rust::utils::{Input, DEFAULT_PROFILE};
+use adventofcode_rust::y2019::assembler::assemble;
+use adventofcode_rust::y2019::computer::Computer;
+use adventofcode_rust::y2019::debugger::{Debugger, Stop};
+use adventofcode_rust::y2019::disassembler::disassemble;
+use adventofcode_rust::y2019::intcode::{format_program, parse_program, Opcode};
+use anyhow::{anyhow, bail, Result};
+use clap::{Parser, Subcommand};
+use std::io::{stdin, stdout, Write};
+use std::path::PathBuf;
+
+/// Instructions executed by `continue` before giving up, for programs that never halt
+const RUN_LIMIT: usize = 100_000_000;
+
+const HELP: &str = "\
+commands:
+  s, step [n]          execute n instructions, 1 by default
+  c, continue          run until a breakpoint, a watchpoint or the halt
+  b, break <at>        stop before the instruction at an address, or with a mnemonic
+  d, delete <at>       remove a breakpoint
+  w, watch <address>   stop after writes to an address
+  u, unwatch <address> remove a watchpoint
+  l, list              show breakpoints and watchpoints
+  i, input <values>    push input values
+  m, memory <address> [count]
+                       show memory values, 8 by default
+  t, trace [n]         show the last executed instructions, 10 by default
+  r, reset             restart the program, keeping breakpoints and watchpoints
+  h, help              show this help
+  q, quit              leave the debugger";
+
+/// Tools to inspect the Intcode programs of 2019
+#[derive(Parser)]
+#[command(name = "intcode")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Prints a program as assembly
+    Disasm {
+        /// Day of the 2019 input to read, or path of a program file
+        program: String,
+        /// Input profile, the directory of `input/` to read days from
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+    },
+    /// Assembles a source file into a comma-separated program
+    Asm {
+        /// Path of the assembly source
+        source: PathBuf,
+    },
+    /// Runs a program in an interactive debugger
+    Debug {
+        /// Day of the 2019 input to read, or path of a program file
+        program: String,
+        /// Input profile, the directory of `input/` to read days from
+        #[arg(long, default_value = DEFAULT_PROFILE)]
+        profile: String,
+        /// Input values pushed before starting, comma-separated
+        #[arg(long, value_delimiter = ',', allow_hyphen_values = true)]
+        input: Vec<isize>,
+    },
+}
+
+fn main() -> Result<()> {
+    pretty_env_logger::init();
+    match Cli::parse().command {
+        Command::Disasm { program, profile } => {
+            let program = parse_program(&read(&program, &profile)?)?;
+            print!("{}", disassemble(&program));
+            Ok(())
+        }
+        Command::Asm { source } => {
+            let program = assemble(&Input::read_file(source)?)?;
+            println!("{}", format_program(&program));
+            Ok(())
+        }
+        Command::Debug {
+            program,
+            profile,
+            input,
+        } => {
+            let mut debugger = Debugger::new(Computer::new(&read(&program, &profile)?)?);
+            input
+                .into_iter()
+                .for_each(|i| debugger.computer_mut().push_input(i));
+            repl(&mut debugger)
+        }
+    }
+}
+
+/// Reads debugger commands from stdin until `quit` or the end of the input
+fn repl(debugger: &mut Debugger) -> Result<()> {
+    println!("{}\n", HELP);
+    show(debugger);
+    loop {
+        print!("(intcode) ");
+        stdout().flush()?;
+        let mut line = String::new();
+        if stdin().read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match command(debugger, &words) {
+            Ok(true) => (),
+            Ok(false) => return Ok(()),
+            Err(err) => println!("error: {:#}", err),
+        }
+    }
+}
+
+/// Runs a command, returns false to quit
+fn command(debugger: &mut Debugger, words: &[&str]) -> Result<bool> {
+    let Some((&name, args)) = words.split_first() else {
+        return Ok(true);
+    };
+    let number = |index: usize, default: Option<usize>| match (args.get(index), default) {
+        (Some(arg), _) => arg.parse().map_err(|_| anyhow!("invalid number {}", arg)),
+        (None, Some(default)) => Ok(default),
+        (None, None) => bail!("missing argument, see help"),
+    };
+    match name {
+        "s" | "step" => {
+            let mut stop = Stop::Stepped;
+            for _ in 0..number(0, Some(1))? {
+                stop = debugger.step()?;
+                if stop != Stop::Stepped {
+                    break;
+                }
+            }
+            println!("{}", stop);
+            show(debugger);
+        }
+        "c" | "continue" => {
+            println!("{}", debugger.run(RUN_LIMIT)?);
+            show(debugger);
+        }
+        "b" | "break" | "d" | "delete" => {
+            let Some(at) = args.first() else {
+                bail!("missing address or mnemonic")
+            };
+            let add = matches!(name, "b" | "break");
+            let changed = match (at.parse::<usize>(), Opcode::from_mnemonic(at)) {
+                (Ok(address), _) if add => debugger.break_at(address),
+                (Ok(address), _) => debugger.remove_breakpoint(address),
+                (_, Some(opcode)) if add => debugger.break_on(opcode),
+                (_, Some(opcode)) => debugger.remove_opcode_breakpoint(opcode),
+                _ => bail!("{} is neither an address nor a mnemonic", at),
+            };
+            if !changed {
+                println!("no change");
+            }
+        }
+        "w" | "watch" => {
+            debugger.watch(number(0, None)?);
+        }
+        "u" | "unwatch" => {
+            if !debugger.remove_watchpoint(number(0, None)?) {
+                println!("no change");
+            }
+        }
+        "l" | "list" => {
+            let breakpoints: Vec<String> = debugger
+                .breakpoints()
+                .map(|a| a.to_string())
+                .chain(debugger.opcode_breakpoints().map(|o| o.mnemonic().into()))
+                .collect();
+            let watchpoints: Vec<String> = debugger.watchpoints().map(|a| a.to_string()).collect();
+            println!("breakpoints: {}", breakpoints.join(" "));
+            println!("watchpoints: {}", watchpoints.join(" "));
+        }
+        "i" | "input" => {
+            for arg in args {
+                let value = arg.parse().map_err(|_| anyhow!("invalid input {}", arg))?;
+                debugger.computer_mut().push_input(value);
+            }
+            show(debugger);
+        }
+        "m" | "memory" => {
+            let start = number(0, None)?;
+            let memory = debugger.computer().memory();
+            let end = (start + number(1, Some(8))?).min(memory.len());
+            for (address, value) in memory.iter().enumerate().take(end).skip(start) {
+                println!("  [{}] {}", address, value);
+            }
+        }
+        "t" | "trace" => {
+            let count = number(0, Some(10))?;
+            let trace: Vec<_> = debugger.trace().collect();
+            for entry in &trace[trace.len().saturating_sub(count)..] {
+                println!("  {}", entry);
+            }
+        }
+        "r" | "reset" => {
+            debugger.reset();
+            show(debugger);
+        }
+        "h" | "help" => println!("{}", HELP),
+        "q" | "quit" => return Ok(false),
+        _ => bail!("unknown command {}, see help", name),
+    }
+    Ok(true)
+}
+
+/// Shows the cursor with the next instruction, and the inputs and outputs
+fn show(debugger: &Debugger) {
+    let computer = debugger.computer();
+    let next = match (
+        debugger.next_instruction(),
+        computer.memory().get(computer.cursor()),
+    ) {
+        (Some(instruction), _) => instruction.to_string(),
+        (None, Some(value)) => format!("data {}", value),
+        (None, None) => "out of memory".to_owned(),
+    };
+    println!("  step    {}", debugger.steps());
+    println!("  cursor  {}: {}", computer.cursor(), next);
+    println!("  input   {:?}", computer.input());
+    println!("  output  {:?}", computer.get_all_output());
+}
+
+/// Reads the input of a 2019 day, or a file when given a path
+fn read(program: &str, profile: &str) -> Result<Input> {
+    let path = match program.parse::<u8>() {
+        Ok(day) => Input::path(profile, "y2019", &format!("d{:02}", day)),
+        Err(_) => PathBuf::from(program),
+    };
+    Input::read_file(path)
+}
@@ -0,0 +1,401 @@
+use crate::utils::{day_key, Answer, Answers, Input};
+use anyhow::{anyhow, bail, Context, Result};
+use log::{debug, info};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!("adventofcode-rust by ", env!("CARGO_PKG_AUTHORS"));
+
+/// Minimum delay between two requests to the server
+const REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Minimal HTTP layer used by the client, so that it can be tested against a stub server
+pub trait Http {
+    /// Sends a GET request with the session cookie, failing on error statuses
+    fn get(&self, url: &str, session: &str) -> Result<String>;
+
+    /// Sends a POST request with the session cookie and a url-encoded form
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String>;
+}
+
+/// Blocking HTTP layer, built on ureq
+pub struct UreqHttp(ureq::Agent);
+
+impl Default for UreqHttp {
+    fn default() -> Self {
+        Self(ureq::AgentBuilder::new().user_agent(USER_AGENT).build())
+    }
+}
+
+impl UreqHttp {
+    fn body(url: &str, response: Result<ureq::Response, ureq::Error>) -> Result<String> {
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, _)) => bail!("{} returned HTTP {}", url, status),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+impl Http for UreqHttp {
+    fn get(&self, url: &str, session: &str) -> Result<String> {
+        let cookie = format!("session={}", session);
+        Self::body(url, self.0.get(url).set("Cookie", &cookie).call())
+    }
+
+    fn post(&self, url: &str, session: &str, form: &[(&str, &str)]) -> Result<String> {
+        let cookie = format!("session={}", session);
+        Self::body(url, self.0.post(url).set("Cookie", &cookie).send_form(form))
+    }
+}
+
+/// Reads the session token from the `AOC_SESSION` environment variable, or the `.session` file
+pub fn session_token() -> Result<String> {
+    match std::env::var("AOC_SESSION") {
+        Ok(token) => Ok(token.trim().to_owned()),
+        Err(_) => Ok(fs::read_to_string(".session")
+            .context("no session token in AOC_SESSION or .session")?
+            .trim()
+            .to_owned()),
+    }
+}
+
+/// Outcome of an answer submission
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    /// The answer is right, it has been stored in the answers file
+    Correct,
+    /// The answer is wrong, with the hint given by the server ("too high", "too low")
+    Rejected(Option<String>),
+    /// The answer was already rejected, it has not been submitted again
+    AlreadyRejected,
+    /// The part is already solved, with a different answer if it is known
+    AlreadySolved(Option<String>),
+    /// An answer was submitted too recently, retry after this delay
+    TooSoon(Duration),
+}
+
+/// Answers rejected by the server for one day
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct Rejected {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    part1: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    part2: Vec<String>,
+}
+
+impl Rejected {
+    fn part(&mut self, part: u8) -> &mut Vec<String> {
+        match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        }
+    }
+}
+
+/// Client for adventofcode.com, caching inputs and puzzle descriptions in a profile directory:
+/// `input/<profile>/yYYYY/dDD` and `input/<profile>/yYYYY/dDD.html`. Correct answers are stored
+/// in `answers.toml`, rejected ones in `rejected.toml`, so that they are not submitted twice.
+/// The time of the last request is kept in `input/<profile>/last_request`, so that successive
+/// runs are throttled too.
+pub struct Client<H: Http> {
+    http: H,
+    base_url: String,
+    session: String,
+    dir: PathBuf,
+    interval: Duration,
+}
+
+impl Client<UreqHttp> {
+    /// Client for adventofcode.com, storing files in the given input profile
+    pub fn new(session: String, profile: &str) -> Self {
+        Client::with_http(
+            UreqHttp::default(),
+            BASE_URL,
+            session,
+            Path::new("input").join(profile),
+        )
+    }
+}
+
+impl<H: Http> Client<H> {
+    /// Client using a custom HTTP layer and server, storing files in `dir`
+    pub fn with_http(http: H, base_url: &str, session: String, dir: PathBuf) -> Self {
+        Self {
+            http,
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session,
+            dir,
+            interval: REQUEST_INTERVAL,
+        }
+    }
+
+    /// Changes the minimum delay between two requests
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Returns the input of a day, downloading it if it is not cached yet
+    pub fn input(&self, year: u16, day: u8) -> Result<Input> {
+        let path = self.day_path(year, day, "");
+        if !path.exists() {
+            let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+            write_file(&path, &self.get(&url)?)?;
+        }
+        Input::read_file(path)
+    }
+
+    /// Returns the puzzle description of a day as HTML, downloading it if it is not cached yet
+    /// or if `refresh` is set, as the second part is only shown once the first one is solved.
+    pub fn description(&self, year: u16, day: u8, refresh: bool) -> Result<String> {
+        static ARTICLE: OnceLock<Result<Regex, String>> = OnceLock::new();
+        let article = regex(&ARTICLE, r"(?s)<article.*?</article>")?;
+        let path = self.day_path(year, day, ".html");
+        if refresh || !path.exists() {
+            let url = format!("{}/{}/day/{}", self.base_url, year, day);
+            let page = self.get(&url)?;
+            let articles: Vec<&str> = article.find_iter(&page).map(|m| m.as_str()).collect();
+            if articles.is_empty() {
+                bail!("no puzzle description in {}", url)
+            }
+            write_file(&path, &articles.join("\n"))?;
+        }
+        Ok(fs::read_to_string(path)?)
+    }
+
+    /// Submits the answer to a part, unless it is already known to be right or wrong
+    pub fn submit(&self, year: u16, day: u8, part: u8, answer: &str) -> Result<Outcome> {
+        static HINT: OnceLock<Result<Regex, String>> = OnceLock::new();
+        static WAIT: OnceLock<Result<Regex, String>> = OnceLock::new();
+        if !(1..=2).contains(&part) {
+            bail!("invalid part {}", part)
+        }
+        let mut answers = Answers::read(self.year_path(year, "answers.toml"))?;
+        let known = answers.get(day);
+        let known = if part == 1 { known.part1 } else { known.part2 };
+        match known {
+            Some(known) if known == answer => return Ok(Outcome::Correct),
+            Some(known) => return Ok(Outcome::AlreadySolved(Some(known))),
+            None => {}
+        }
+        let mut rejected = self.rejected(year)?;
+        let day_rejected = rejected.entry(day_key(day)).or_default();
+        if day_rejected.part(part).iter().any(|r| r == answer) {
+            return Ok(Outcome::AlreadyRejected);
+        }
+
+        let url = format!("{}/{}/day/{}/answer", self.base_url, year, day);
+        let level = part.to_string();
+        let page = self.post(&url, &[("level", &level), ("answer", answer)])?;
+        if page.contains("That's the right answer") {
+            let output = match part {
+                1 => (Answer::from(answer), Answer::Unsolved),
+                _ => (Answer::Unsolved, Answer::from(answer)),
+            };
+            answers.record(day, &output);
+            fs::create_dir_all(self.year_path(year, ""))?;
+            answers.save()?;
+            Ok(Outcome::Correct)
+        } else if page.contains("That's not the right answer") {
+            day_rejected.part(part).push(answer.to_owned());
+            write_file(
+                &self.year_path(year, "rejected.toml"),
+                &toml::to_string_pretty(&rejected)?,
+            )?;
+            let hint = regex(&HINT, r"your answer is (too \w+)")?
+                .captures(&page)
+                .map(|c| c[1].to_owned());
+            Ok(Outcome::Rejected(hint))
+        } else if page.contains("You gave an answer too recently") {
+            let wait = match regex(&WAIT, r"You have (?:(\d+)m )?(\d+)s left")?.captures(&page) {
+                Some(c) => {
+                    let minutes: u64 = c.get(1).map_or(Ok(0), |m| m.as_str().parse())?;
+                    Duration::from_secs(minutes * 60 + c[2].parse::<u64>()?)
+                }
+                None => self.interval,
+            };
+            Ok(Outcome::TooSoon(wait))
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Outcome::AlreadySolved(None))
+        } else {
+            bail!("unexpected response from {}", url)
+        }
+    }
+
+    fn rejected(&self, year: u16) -> Result<BTreeMap<String, Rejected>> {
+        match fs::read_to_string(self.year_path(year, "rejected.toml")) {
+            Ok(content) => Ok(toml::from_str(&content)?),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    fn get(&self, url: &str) -> Result<String> {
+        self.throttle()?;
+        info!["Downloading {}", url];
+        self.http.get(url, &self.session)
+    }
+
+    fn post(&self, url: &str, form: &[(&str, &str)]) -> Result<String> {
+        self.throttle()?;
+        info!["Posting to {}", url];
+        self.http.post(url, &self.session, form)
+    }
+
+    /// Waits until the minimum delay since the last request has elapsed, and records the time
+    /// of the new one
+    fn throttle(&self) -> Result<()> {
+        let wait = self.wait()?;
+        if !wait.is_zero() {
+            debug!["Waiting {:?} before the next request", wait];
+            std::thread::sleep(wait);
+        }
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+        write_file(&self.dir.join("last_request"), &now.as_millis().to_string())
+    }
+
+    /// Delay left before the next request, from the time recorded by the last one
+    fn wait(&self) -> Result<Duration> {
+        let path = self.dir.join("last_request");
+        let millis: u64 = match fs::read_to_string(&path) {
+            Ok(content) => content
+                .trim()
+                .parse()
+                .with_context(|| format!("invalid time in {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Duration::ZERO),
+            Err(err) => return Err(err.into()),
+        };
+        let last = UNIX_EPOCH + Duration::from_millis(millis);
+        // A time in the future waits for the whole interval
+        let elapsed = SystemTime::now().duration_since(last).unwrap_or_default();
+        Ok(self.interval.saturating_sub(elapsed))
+    }
+
+    fn year_path(&self, year: u16, file: &str) -> PathBuf {
+        self.dir.join(format!("y{}", year)).join(file)
+    }
+
+    fn day_path(&self, year: u16, day: u8, extension: &str) -> PathBuf {
+        self.year_path(year, &format!("{}{}", day_key(day), extension))
+    }
+}
+
+/// Compiles a regex once, keeping it in `cache`
+fn regex<'a>(cache: &'a OnceLock<Result<Regex, String>>, pattern: &str) -> Result<&'a Regex> {
+    let compiled = cache.get_or_init(|| Regex::new(pattern).map_err(|e| e.to_string()));
+    compiled.as_ref().map_err(|err| anyhow!("{}", err))
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    debug!["Writing {}", path.display()];
+    Ok(fs::write(path, content)?)
+}
+
+/// Serves canned responses on a local port, returning its url and the received requests
+#[cfg(test)]
+fn stub_server(responses: Vec<&'static str>) -> (String, std::sync::mpsc::Receiver<String>) {
+    use std::io::{BufRead, BufReader, Read, Write};
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        for body in responses {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(value) = line.to_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+                request += &line;
+                if line.trim().is_empty() {
+                    break;
+                }
+            }
+            let mut content = vec![0; length];
+            reader.read_exact(&mut content).unwrap();
+            request += &String::from_utf8(content).unwrap();
+            sender.send(request).unwrap();
+            let status = match body {
+                "" => "404 Not Found",
+                _ => "200 OK",
+            };
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (url, receiver)
+}
+
+#[test]
+fn test_client() -> Result<()> {
+    let _ = pretty_env_logger::try_init();
+    let dir = std::env::temp_dir().join(format!("aoc-client-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    let (url, requests) = stub_server(vec![
+        "1721\n979\n",
+        "<html><main><article><h2>Day 1</h2></article></main></html>",
+        "<main><p>That's not the right answer; your answer is too low.</p></main>",
+        "<main><p>You gave an answer too recently. You have 1m 5s left to wait.</p></main>",
+        "<main><p>That's the right answer!</p></main>",
+        "",
+    ]);
+    let client = Client::with_http(UreqHttp::default(), &url, "secret".into(), dir.clone())
+        .with_interval(Duration::ZERO);
+
+    // Inputs and descriptions are only downloaded once
+    assert_eq!("1721\n979\n", client.input(2020, 1)?.all());
+    assert_eq!("1721\n979\n", client.input(2020, 1)?.all());
+    let request = requests.recv()?;
+    assert!(request.starts_with("GET /2020/day/1/input "));
+    assert!(request.contains("session=secret"));
+    let description = "<article><h2>Day 1</h2></article>";
+    assert_eq!(description, client.description(2020, 1, false)?);
+    assert_eq!(description, client.description(2020, 1, false)?);
+    assert!(requests.recv()?.starts_with("GET /2020/day/1 "));
+
+    // Rejected answers are not submitted twice, correct ones are recorded
+    let rejected = Outcome::Rejected(Some("too low".into()));
+    assert_eq!(rejected, client.submit(2020, 1, 1, "42")?);
+    assert!(requests.recv()?.ends_with("level=1&answer=42"));
+    assert_eq!(Outcome::AlreadyRejected, client.submit(2020, 1, 1, "42")?);
+    let wait = Outcome::TooSoon(Duration::from_secs(65));
+    assert_eq!(wait, client.submit(2020, 1, 1, "514579")?);
+    assert_eq!(Outcome::Correct, client.submit(2020, 1, 1, "514579")?);
+    assert_eq!(Outcome::Correct, client.submit(2020, 1, 1, "514579")?);
+    let known = Outcome::AlreadySolved(Some("514579".into()));
+    assert_eq!(known, client.submit(2020, 1, 1, "43")?);
+    let answers = Answers::read(dir.join("y2020/answers.toml"))?;
+    assert_eq!(Some("514579".into()), answers.get(1).part1);
+
+    // Errors are reported
+    assert!(client.input(2020, 2).is_err());
+
+    // The last request is remembered by other clients of the profile
+    assert!(dir.join("last_request").exists());
+    let client = Client::with_http(UreqHttp::default(), &url, "secret".into(), dir.clone())
+        .with_interval(Duration::from_secs(3600));
+    assert!(client.wait()? > Duration::from_secs(3500));
+    fs::remove_dir_all(&dir)?;
+    Ok(())
+}
@@ -1,3 +1,5 @@
+pub mod client;
+pub mod registry;
 pub mod utils;
 pub mod y2019;
 pub mod y2020;
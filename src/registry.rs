@@ -0,0 +1,51 @@
+use crate::utils::Solution;
+
+/// Lists the solutions of all years, sorted by year, day and variant
+pub fn solutions() -> Vec<Box<dyn Solution>> {
+    let mut solutions = crate::y2019::solutions();
+    solutions.extend(crate::y2020::solutions());
+    solutions.extend(crate::y2021::solutions());
+    solutions.extend(crate::y2022::solutions());
+    solutions
+}
+
+/// Finds the solution for a given day, pass no variant to get the main implementation
+pub fn find(year: u16, day: u8, variant: Option<&str>) -> Option<Box<dyn Solution>> {
+    solutions()
+        .into_iter()
+        .find(|s| s.year() == year && s.day() == day && s.variant() == variant)
+}
+
+/// Lists all implementations of a day, starting with the main one
+pub fn variants(year: u16, day: u8) -> Vec<Box<dyn Solution>> {
+    solutions()
+        .into_iter()
+        .filter(|s| s.year() == year && s.day() == day)
+        .collect()
+}
+
+#[test]
+fn test_registry_order() {
+    let names: Vec<String> = solutions().iter().map(|s| s.name()).collect();
+    let mut sorted = names.clone();
+    sorted.sort();
+    sorted.dedup();
+    assert_eq!(sorted, names);
+}
+
+#[test]
+fn test_registry_find() {
+    let solution = find(2020, 5, Some("opt")).expect("missing solution");
+    assert_eq!("y2020::d05_opt", solution.name());
+    assert!(find(2020, 5, None).is_some());
+    assert!(find(2020, 26, None).is_none());
+
+    let names: Vec<String> = variants(2020, 5).iter().map(|s| s.name()).collect();
+    assert_eq!(vec!["y2020::d05", "y2020::d05_opt"], names);
+
+    // Every registered day has a main implementation and an input
+    for solution in solutions() {
+        assert!(find(solution.year(), solution.day(), None).is_some());
+        assert!(solution.input().is_ok(), "no input for {}", solution.name());
+    }
+}
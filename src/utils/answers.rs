@@ -0,0 +1,216 @@
+use crate::utils::{profiles, Answer, Solution, DEFAULT_PROFILE};
+use anyhow::{bail, Context, Result};
+use log::{debug, info};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::PathBuf;
+
+/// Known answers for both parts of a day, None when the answer is still unknown
+#[derive(Debug, Default, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part1: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub part2: Option<String>,
+}
+
+/// Result of checking a computed answer against the known one
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String, actual: String },
+    Unknown,
+}
+
+impl Verdict {
+    /// Compares an answer with the expected one, if known
+    pub fn check(expected: &Option<String>, actual: &Answer) -> Self {
+        let actual = actual.to_string();
+        match expected {
+            None => Verdict::Unknown,
+            Some(expected) if *expected == actual => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.clone(),
+                actual,
+            },
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "pass"),
+            Verdict::Fail { expected, actual } => {
+                write!(f, "FAIL (expected {}, got {})", expected, actual)
+            }
+            Verdict::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Answers database for one year of a profile, stored as `input/<profile>/yYYYY/answers.toml`
+/// with one table per day:
+///
+/// ```toml
+/// [d13]
+/// part1 = "207"
+/// # part2 is not known yet
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    path: PathBuf,
+    days: BTreeMap<String, DayAnswers>,
+}
+
+impl Answers {
+    /// Reads the answers for a year, a missing file means all answers are unknown
+    pub fn open(profile: &str, year: &str) -> Result<Self> {
+        Self::read(PathBuf::from(format![
+            "input/{}/{}/answers.toml",
+            profile, year
+        ]))
+    }
+
+    /// Reads answers from an arbitrary file, a missing file means all answers are unknown
+    pub fn read(path: PathBuf) -> Result<Self> {
+        debug!["Reading answers from {}", path.display()];
+        let days = match fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, days })
+    }
+
+    /// Reads the answers for the year of the given solution
+    pub fn open_for(profile: &str, solution: &dyn Solution) -> Result<Self> {
+        Self::open(profile, &format!("y{}", solution.year()))
+    }
+
+    /// Known answers for a day, shared by all its variants
+    pub fn get(&self, day: u8) -> DayAnswers {
+        self.days.get(&day_key(day)).cloned().unwrap_or_default()
+    }
+
+    /// Checks the computed answers of a day against the known ones
+    pub fn verify(&self, day: u8, output: &(Answer, Answer)) -> (Verdict, Verdict) {
+        let expected = self.get(day);
+        (
+            Verdict::check(&expected.part1, &output.0),
+            Verdict::check(&expected.part2, &output.1),
+        )
+    }
+
+    /// Stores computed answers for the unknown parts of a day, known answers are never
+    /// overwritten and unsolved parts are skipped. Returns whether any answer was added.
+    pub fn record(&mut self, day: u8, output: &(Answer, Answer)) -> bool {
+        let known = self.days.entry(day_key(day)).or_default();
+        let mut changed = false;
+        for (expected, actual) in [(&mut known.part1, &output.0), (&mut known.part2, &output.1)] {
+            if expected.is_none() && *actual != Answer::Unsolved {
+                *expected = Some(actual.to_string());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Writes the answers back to their file
+    pub fn save(&self) -> Result<()> {
+        info!["Writing answers to {}", self.path.display()];
+        fs::write(&self.path, toml::to_string_pretty(&self.days)?)?;
+        Ok(())
+    }
+}
+
+/// Name of a day in answer files and cached puzzle files: `dDD`
+pub(crate) fn day_key(day: u8) -> String {
+    format!("d{:02}", day)
+}
+
+/// Runs all implementations of a day on the input of every profile that has one, the base day
+/// first, and fails if an answer does not match the known one or if a variant disagrees with
+/// the base day. Unknown answers are only logged.
+pub fn check_answers(solutions: &[Box<dyn Solution>]) -> Result<()> {
+    let solution = match solutions.first() {
+        Some(first) => first.as_ref(),
+        None => bail!("no solution to check"),
+    };
+    for profile in profiles()? {
+        if profile == DEFAULT_PROFILE || solution.input_path(&profile).exists() {
+            check_profile(&profile, solutions).with_context(|| format!("profile {}", profile))?;
+        }
+    }
+    Ok(())
+}
+
+fn check_profile(profile: &str, solutions: &[Box<dyn Solution>]) -> Result<()> {
+    let (solution, variants) = (solutions[0].as_ref(), &solutions[1..]);
+    let input = solution.input_from(profile)?;
+    let output = solution.run(&input)?;
+    let verdicts = Answers::open_for(profile, solution)?.verify(solution.day(), &output);
+    info!(
+        "{} ({}): part 1 {}, part 2 {}",
+        solution.name(),
+        profile,
+        verdicts.0,
+        verdicts.1
+    );
+    for (part, verdict) in [(1, verdicts.0), (2, verdicts.1)] {
+        if let Verdict::Fail { expected, actual } = verdict {
+            bail!("part {} returned {}, expected {}", part, actual, expected);
+        }
+    }
+    for variant in variants {
+        if (variant.year(), variant.day()) != (solution.year(), solution.day()) {
+            bail!("{} is not a variant of {}", variant.name(), solution.name())
+        }
+        let variant_output = variant.run(&input)?;
+        if variant_output != output {
+            bail!(
+                "{} returned ({}, {}), but {} returned ({}, {})",
+                variant.name(),
+                variant_output.0,
+                variant_output.1,
+                solution.name(),
+                output.0,
+                output.1
+            );
+        }
+        info!("{}: same answers as {}", variant.name(), solution.name());
+    }
+    Ok(())
+}
+
+#[test]
+fn test_verify_and_record() -> Result<()> {
+    let mut answers = Answers {
+        path: PathBuf::new(),
+        days: toml::from_str("[d13]\npart1 = \"207\"\n")?,
+    };
+    let output = (Answer::Integer(207), Answer::Integer(1058443396696792));
+    assert_eq!(
+        (Verdict::Pass, Verdict::Unknown),
+        answers.verify(13, &output)
+    );
+    assert_eq!(
+        (Verdict::Unknown, Verdict::Unknown),
+        answers.verify(12, &output)
+    );
+
+    let wrong = (Answer::Integer(208), Answer::Unsolved);
+    assert!(matches!(answers.verify(13, &wrong).0, Verdict::Fail { .. }));
+    assert!(!answers.record(13, &wrong));
+
+    assert!(answers.record(13, &output));
+    assert!(!answers.record(13, &wrong));
+    assert_eq!((Verdict::Pass, Verdict::Pass), answers.verify(13, &output));
+    assert_eq!(
+        "[d13]\npart1 = \"207\"\npart2 = \"1058443396696792\"\n",
+        toml::to_string_pretty(&answers.days)?
+    );
+    Ok(())
+}
@@ -0,0 +1,165 @@
+use crate::utils::{Cycle, Grid};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Iterates on the neighbours of a cell, as selected by the neighbourhood function
+pub struct Neighbours<'a, T> {
+    cells: &'a [T],
+    indexes: std::slice::Iter<'a, usize>,
+}
+
+impl<'a, T> Iterator for Neighbours<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.indexes.next().map(|i| &self.cells[*i])
+    }
+}
+
+/// Cellular automaton on a grid, where each step computes every cell from its previous value
+/// and the previous values of its neighbours. The neighbourhood of each cell is computed once
+/// from the initial grid, and steps write into a second grid that is then swapped.
+pub struct Automaton<T, R> {
+    current: Grid<T>,
+    next: Grid<T>,
+    neighbours: Vec<Vec<usize>>,
+    rule: R,
+    steps: usize,
+}
+
+impl<T, R> Automaton<T, R>
+where
+    T: Clone + Eq,
+    R: Fn(&T, Neighbours<'_, T>) -> T,
+{
+    /// Creates an automaton, `neighbourhood` returns the positions of the neighbours of a cell
+    pub fn new<N>(grid: Grid<T>, neighbourhood: N, rule: R) -> Self
+    where
+        N: Fn(&Grid<T>, usize, usize) -> Vec<(usize, usize)>,
+    {
+        let width = grid.width();
+        let neighbours = grid
+            .positions()
+            .map(|(x, y)| {
+                let positions = neighbourhood(&grid, x, y);
+                positions.into_iter().map(|(x, y)| y * width + x).collect()
+            })
+            .collect();
+        Self {
+            next: grid.clone(),
+            current: grid,
+            neighbours,
+            rule,
+            steps: 0,
+        }
+    }
+
+    /// Current state of the automaton
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    /// Number of steps run since the initial state
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Runs one step, returns whether any cell changed
+    pub fn step(&mut self) -> bool {
+        let mut changed = false;
+        let cells = self.current.cells();
+        for ((cell, next), neighbours) in cells
+            .iter()
+            .zip(self.next.cells_mut())
+            .zip(&self.neighbours)
+        {
+            let neighbours = Neighbours {
+                cells,
+                indexes: neighbours.iter(),
+            };
+            *next = (self.rule)(cell, neighbours);
+            changed |= next != cell;
+        }
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.steps += 1;
+        changed
+    }
+
+    /// Runs a given number of steps
+    pub fn run(&mut self, steps: usize) -> &Grid<T> {
+        for _ in 0..steps {
+            self.step();
+        }
+        &self.current
+    }
+
+    /// Runs until a step does not change any cell, returns the number of steps that changed
+    /// the grid, or None if it is still changing after `max_steps`.
+    pub fn run_until_stable(&mut self, max_steps: usize) -> Option<usize> {
+        for _ in 0..max_steps {
+            if !self.step() {
+                return Some(self.steps - 1);
+            }
+        }
+        None
+    }
+
+    /// Runs until a previous state repeats, or None if none repeated after `max_steps`.
+    /// A stable state is a cycle of length 1.
+    pub fn find_cycle(&mut self, max_steps: usize) -> Option<Cycle>
+    where
+        T: Hash,
+    {
+        let mut seen = HashMap::new();
+        seen.insert(self.current.clone(), self.steps);
+        for _ in 0..max_steps {
+            self.step();
+            if let Some(start) = seen.insert(self.current.clone(), self.steps) {
+                return Some(Cycle {
+                    start,
+                    length: self.steps - start,
+                });
+            }
+        }
+        None
+    }
+}
+
+/// Rule of Conway's game of life
+#[cfg(test)]
+fn life(alive: &bool, neighbours: Neighbours<'_, bool>) -> bool {
+    let count = neighbours.filter(|n| **n).count();
+    count == 3 || (*alive && count == 2)
+}
+
+#[test]
+fn test_automaton() -> anyhow::Result<()> {
+    let parse = |grid| Grid::parse(&crate::utils::Input::from(grid), |c| Ok(c == '#'));
+    let render = |grid: &Grid<bool>| grid.map(|c| if *c { '#' } else { '.' }).to_string();
+    let moore = |grid: &Grid<bool>, x, y| grid.neighbours8(x, y).collect();
+
+    // Blinker oscillates with a period of 2
+    let mut blinker = Automaton::new(parse(".....\n..#..\n..#..\n..#..\n.....\n")?, moore, life);
+    assert_eq!(
+        ".....\n.....\n.###.\n.....\n.....\n",
+        render(blinker.run(1))
+    );
+    let cycle = Cycle {
+        start: 1,
+        length: 2,
+    };
+    assert_eq!(Some(cycle), blinker.find_cycle(10));
+    assert_eq!(3, blinker.steps());
+    assert_eq!(None, blinker.run_until_stable(10));
+
+    // Block is stable, a lone cell dies at the first step
+    let mut block = Automaton::new(parse("....\n.##.\n.##.\n....\n....\n#...\n")?, moore, life);
+    assert_eq!(Some(1), block.run_until_stable(10));
+    assert_eq!("....\n.##.\n.##.\n....\n....\n....\n", render(block.grid()));
+    let cycle = Cycle {
+        start: 2,
+        length: 1,
+    };
+    assert_eq!(Some(cycle), block.find_cycle(10));
+    Ok(())
+}
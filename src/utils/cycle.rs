@@ -0,0 +1,174 @@
+use anyhow::{bail, Result};
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A repeating sequence of states: the state after `start + length` steps is the same
+/// as the state after `start` steps.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Earliest step with the same state as after `steps` steps
+    pub fn equivalent(&self, steps: usize) -> usize {
+        match steps < self.start {
+            true => steps,
+            false => self.start + (steps - self.start) % self.length,
+        }
+    }
+}
+
+/// Finds the cycle of a deterministic simulation with Brent's algorithm, which only keeps two
+/// states in memory but runs the simulation about three times up to the cycle. Returns None
+/// if no state repeated after `max_steps`.
+pub fn brent<S, F>(initial: S, mut step: F, max_steps: usize) -> Option<Cycle>
+where
+    S: Clone + Eq,
+    F: FnMut(&S) -> S,
+{
+    // Find the cycle length, by looking for a repeat of the state at each power of two
+    let (mut power, mut length) = (1, 1);
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    let mut steps = 1;
+    while tortoise != hare {
+        if steps >= max_steps {
+            return None;
+        }
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+        steps += 1;
+    }
+
+    // Find the start, with the hare `length` steps ahead of the tortoise
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        start += 1;
+    }
+    Some(Cycle { start, length })
+}
+
+/// States of a deterministic simulation, recorded until the first repeated state. Gives the
+/// state after any number of steps without running them.
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    states: Vec<S>,
+    cycle: Cycle,
+}
+
+impl<S: Clone + Eq + Hash> History<S> {
+    /// Runs the simulation until a state repeats, failing if none did after `max_steps`
+    pub fn record<F: FnMut(&S) -> S>(initial: S, mut step: F, max_steps: usize) -> Result<Self> {
+        let mut seen = HashMap::from([(initial.clone(), 0)]);
+        let mut states = vec![initial];
+        while states.len() <= max_steps {
+            let next = step(&states[states.len() - 1]);
+            if let Some(start) = seen.get(&next) {
+                let cycle = Cycle {
+                    start: *start,
+                    length: states.len() - start,
+                };
+                return Ok(Self { states, cycle });
+            }
+            seen.insert(next.clone(), states.len());
+            states.push(next);
+        }
+        bail!("no repeated state after {} steps", max_steps)
+    }
+
+    pub fn cycle(&self) -> Cycle {
+        self.cycle
+    }
+
+    /// States from the initial one to the last one before the first repeat
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+
+    /// State after a number of steps
+    pub fn state(&self, steps: usize) -> &S {
+        &self.states[self.cycle.equivalent(steps)]
+    }
+
+    /// Value of a metric after a number of steps, for metrics that are not part of the state
+    /// but grow by the same amount on each loop of the cycle, like a height in a simulation
+    /// whose state is relative to the top. `values` are the metric after each step, from the
+    /// initial state to the first repeated state included, usually recorded by `step`.
+    pub fn extrapolate(&self, steps: usize, values: &[i64]) -> Result<i64> {
+        let Cycle { start, length } = self.cycle;
+        if values.len() <= start + length {
+            bail!(
+                "{} values given, expected {}",
+                values.len(),
+                start + length + 1
+            )
+        }
+        if steps < values.len() {
+            return Ok(values[steps]);
+        }
+        let growth = values[start + length] - values[start];
+        let loops = ((steps - start) / length) as i64;
+        Ok(values[self.cycle.equivalent(steps)] + loops * growth)
+    }
+}
+
+#[test]
+fn test_cycle() -> Result<()> {
+    // 2, 4, 16, 256 % 100 = 56, 36, 96, 16, ... loops from 16 with a length of 4
+    let step = |n: &u32| n * n % 100;
+    let cycle = Cycle {
+        start: 2,
+        length: 4,
+    };
+    assert_eq!(Some(cycle), brent(2, step, 100));
+    assert_eq!(None, brent(2, step, 3));
+    assert_eq!(
+        Some(Cycle {
+            start: 0,
+            length: 1
+        }),
+        brent(0, step, 100)
+    );
+
+    let history = History::record(2, step, 100)?;
+    assert_eq!(cycle, history.cycle());
+    assert_eq!(&[2, 4, 16, 56, 36, 96], history.states());
+    assert_eq!(&16, history.state(6));
+    assert_eq!(&56, history.state(1_000_000_000_003));
+    assert!(History::record(2, step, 5).is_err());
+
+    // Counting the sum of the states, which grows by 204 on each loop
+    let mut sums = vec![2];
+    let history = History::record(
+        2,
+        |n| {
+            let next = step(n);
+            sums.push(sums.last().unwrap() + next as i64);
+            next
+        },
+        100,
+    )?;
+    assert_eq!(16 + 56 + 36 + 96, sums[6] - sums[2]);
+    assert_eq!(sums[5], history.extrapolate(5, &sums)?);
+    assert_eq!(sums[3] + 204 * 2, history.extrapolate(11, &sums)?);
+    assert_eq!(
+        22 + 204 * 250_000_000_000,
+        history.extrapolate(1_000_000_000_002, &sums)?
+    );
+    assert!(history.extrapolate(7, &sums[..4]).is_err());
+    Ok(())
+}
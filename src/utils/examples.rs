@@ -0,0 +1,77 @@
+use crate::utils::{DayAnswers, Input, Solution, Verdict, EXAMPLES_DIR};
+use anyhow::{anyhow, bail, Context, Result};
+use log::info;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// A puzzle example, stored in `input/examples/yYYYY/dDD/` along the other examples of the day:
+/// its input is in `<name>.txt`, and `answers.toml` holds one table of expected answers per
+/// example. Parts without an expected answer are not computed.
+///
+/// ```toml
+/// [larger]
+/// part1 = "31"
+/// ```
+pub struct Example {
+    pub name: String,
+    pub input: Input,
+    pub expected: DayAnswers,
+}
+
+impl Example {
+    /// Reads a single example of a day, it must have at least one expected answer
+    pub fn open(year: &str, day: &str, name: &str) -> Result<Self> {
+        let dir = PathBuf::from(format!["input/{}/{}/{}", EXAMPLES_DIR, year, day]);
+        let answers = fs::read_to_string(dir.join("answers.toml"))
+            .with_context(|| format!("missing answers for {}/{} examples", year, day))?;
+        let mut answers: BTreeMap<String, DayAnswers> = toml::from_str(&answers)?;
+        let expected = answers
+            .remove(name)
+            .ok_or_else(|| anyhow!("no expected answers for example {}", name))?;
+        if expected.part1.is_none() && expected.part2.is_none() {
+            bail!("no expected answers for example {}", name)
+        }
+        Ok(Self {
+            name: name.to_owned(),
+            input: Input::read_file(dir.join(format!("{}.txt", name)))?
+                .with_day(&format!("{}::{} example {}", year, day, name)),
+            expected,
+        })
+    }
+
+    /// Runs all implementations of a day on this example, failing if none is given
+    pub fn check_all(&self, solutions: &[Box<dyn Solution>]) -> Result<()> {
+        if solutions.is_empty() {
+            bail!("no solution for example {}", self.name)
+        }
+        for solution in solutions {
+            self.check(solution.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Runs a solution on this example and fails if an answer does not match
+    pub fn check(&self, solution: &dyn Solution) -> Result<()> {
+        let parsed = solution.parse(&self.input)?;
+        for (part, expected) in [(1, &self.expected.part1), (2, &self.expected.part2)] {
+            let actual = match (part, expected) {
+                (_, None) => continue,
+                (1, _) => solution.part1(parsed.as_ref())?,
+                _ => solution.part2(parsed.as_ref())?,
+            };
+            let verdict = Verdict::check(expected, &actual);
+            info!(
+                "{} example {}: part {} {}",
+                solution.name(),
+                self.name,
+                part,
+                verdict
+            );
+            if let Verdict::Fail { expected, actual } = verdict {
+                bail!("part {} returned {}, expected {}", part, actual, expected);
+            }
+        }
+        Ok(())
+    }
+}
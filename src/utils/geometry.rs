@@ -0,0 +1,322 @@
+use anyhow::{bail, Result};
+use std::fmt::{Display, Formatter};
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// Position in a 2D or 3D space. The `y` axis points down, like the rows of a `Grid`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Point<const N: usize = 2>(pub [isize; N]);
+
+/// Displacement between two points
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Vector<const N: usize = 2>(pub [isize; N]);
+
+// Aliases to call the constructors, the default dimension does not apply to expressions
+pub type Point2 = Point<2>;
+pub type Point3 = Point<3>;
+pub type Vector2 = Vector<2>;
+pub type Vector3 = Vector<3>;
+
+/// Implements the accessors of the named coordinates, only for the dimensions having them
+macro_rules! impl_coordinates {
+    ($type:ident<$n:literal>, $($name:ident: $index:literal),+) => {
+        impl $type<$n> {
+            $(
+                pub fn $name(&self) -> isize {
+                    self.0[$index]
+                }
+            )+
+        }
+    };
+}
+
+impl_coordinates!(Point<2>, x: 0, y: 1);
+impl_coordinates!(Point<3>, x: 0, y: 1, z: 2);
+impl_coordinates!(Vector<2>, x: 0, y: 1);
+impl_coordinates!(Vector<3>, x: 0, y: 1, z: 2);
+
+impl Point<2> {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self([x, y])
+    }
+}
+
+impl Point<3> {
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self([x, y, z])
+    }
+}
+
+impl Vector<2> {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self([x, y])
+    }
+
+    /// Rotates clockwise by a multiple of 90°, negative angles rotating counter-clockwise
+    pub fn rotate(self, degrees: isize) -> Result<Self> {
+        let [x, y] = self.0;
+        Ok(match quarter_turns(degrees)? {
+            0 => self,
+            1 => Self::new(-y, x),
+            2 => Self::new(-x, -y),
+            _ => Self::new(y, -x),
+        })
+    }
+}
+
+impl Vector<3> {
+    pub const fn new(x: isize, y: isize, z: isize) -> Self {
+        Self([x, y, z])
+    }
+}
+
+impl<const N: usize> Point<N> {
+    pub const ORIGIN: Self = Self([0; N]);
+
+    /// Sum of the distances on each axis
+    pub fn manhattan(&self, other: &Self) -> usize {
+        (*self - *other).manhattan()
+    }
+
+    /// Largest distance on any axis, counting diagonal moves as one step
+    pub fn chebyshev(&self, other: &Self) -> usize {
+        (*self - *other).chebyshev()
+    }
+}
+
+impl<const N: usize> Vector<N> {
+    pub const ZERO: Self = Self([0; N]);
+
+    /// Manhattan length of the vector
+    pub fn manhattan(&self) -> usize {
+        self.0.iter().map(|c| c.unsigned_abs()).sum()
+    }
+
+    /// Chebyshev length of the vector
+    pub fn chebyshev(&self) -> usize {
+        self.0.iter().map(|c| c.unsigned_abs()).max().unwrap_or(0)
+    }
+}
+
+fn zip_with<const N: usize>(
+    a: [isize; N],
+    b: [isize; N],
+    f: fn(isize, isize) -> isize,
+) -> [isize; N] {
+    let mut out = a;
+    for (o, b) in out.iter_mut().zip(b) {
+        *o = f(*o, b);
+    }
+    out
+}
+
+impl<const N: usize> Add<Vector<N>> for Point<N> {
+    type Output = Point<N>;
+
+    fn add(self, rhs: Vector<N>) -> Point<N> {
+        Point(zip_with(self.0, rhs.0, isize::add))
+    }
+}
+
+impl<const N: usize> Sub<Vector<N>> for Point<N> {
+    type Output = Point<N>;
+
+    fn sub(self, rhs: Vector<N>) -> Point<N> {
+        Point(zip_with(self.0, rhs.0, isize::sub))
+    }
+}
+
+impl<const N: usize> Sub for Point<N> {
+    type Output = Vector<N>;
+
+    fn sub(self, rhs: Point<N>) -> Vector<N> {
+        Vector(zip_with(self.0, rhs.0, isize::sub))
+    }
+}
+
+impl<const N: usize> AddAssign<Vector<N>> for Point<N> {
+    fn add_assign(&mut self, rhs: Vector<N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> SubAssign<Vector<N>> for Point<N> {
+    fn sub_assign(&mut self, rhs: Vector<N>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: usize> Add for Vector<N> {
+    type Output = Vector<N>;
+
+    fn add(self, rhs: Vector<N>) -> Vector<N> {
+        Vector(zip_with(self.0, rhs.0, isize::add))
+    }
+}
+
+impl<const N: usize> Sub for Vector<N> {
+    type Output = Vector<N>;
+
+    fn sub(self, rhs: Vector<N>) -> Vector<N> {
+        Vector(zip_with(self.0, rhs.0, isize::sub))
+    }
+}
+
+impl<const N: usize> AddAssign for Vector<N> {
+    fn add_assign(&mut self, rhs: Vector<N>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: usize> Mul<isize> for Vector<N> {
+    type Output = Vector<N>;
+
+    fn mul(self, rhs: isize) -> Vector<N> {
+        Vector(self.0.map(|c| c * rhs))
+    }
+}
+
+impl<const N: usize> Neg for Vector<N> {
+    type Output = Vector<N>;
+
+    fn neg(self) -> Vector<N> {
+        Vector(self.0.map(|c| -c))
+    }
+}
+
+impl<const N: usize> Display for Point<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let coordinates: Vec<String> = self.0.iter().map(isize::to_string).collect();
+        write!(f, "({})", coordinates.join(","))
+    }
+}
+
+/// Number of clockwise quarter turns in an angle, from 0 to 3
+fn quarter_turns(degrees: isize) -> Result<isize> {
+    if degrees % 90 != 0 {
+        bail!("{}° is not a multiple of 90°", degrees)
+    }
+    Ok((degrees / 90).rem_euclid(4))
+}
+
+/// Compass direction in a 2D space, North being up
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    /// All directions, clockwise from North
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// Unit vector pointing in this direction
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::North => Vector2::new(0, -1),
+            Direction::East => Vector2::new(1, 0),
+            Direction::South => Vector2::new(0, 1),
+            Direction::West => Vector2::new(-1, 0),
+        }
+    }
+
+    pub fn right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    /// Turns clockwise by a multiple of 90°, negative angles turning counter-clockwise
+    pub fn rotate(self, degrees: isize) -> Result<Self> {
+        Ok(Self::ALL[(self as usize + quarter_turns(degrees)? as usize) % 4])
+    }
+}
+
+/// Parses compass letters `N/E/S/W` and relative letters `U/R/D/L`
+impl TryFrom<char> for Direction {
+    type Error = anyhow::Error;
+
+    fn try_from(c: char) -> Result<Self> {
+        Ok(match c {
+            'N' | 'U' => Direction::North,
+            'E' | 'R' => Direction::East,
+            'S' | 'D' => Direction::South,
+            'W' | 'L' => Direction::West,
+            _ => bail!("invalid direction {}", c),
+        })
+    }
+}
+
+impl FromStr for Direction {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => bail!("invalid direction {}", s),
+        }
+    }
+}
+
+#[test]
+fn test_points_and_vectors() -> Result<()> {
+    let a = Point2::new(1, 2);
+    let b = Point2::new(4, -2);
+    assert_eq!(Vector2::new(3, -4), b - a);
+    assert_eq!(b, a + (b - a));
+    assert_eq!(a, b - (b - a));
+    assert_eq!(7, a.manhattan(&b));
+    assert_eq!(4, a.chebyshev(&b));
+    assert_eq!(Vector2::new(-6, 8), -(b - a) * 2);
+    assert_eq!("(1,2)", a.to_string());
+
+    let mut c = Point3::new(1, 2, 3);
+    c += Vector3::new(1, 1, 1) * 3;
+    assert_eq!(Point3::new(4, 5, 6), c);
+    assert_eq!(15, c.manhattan(&Point3::ORIGIN));
+    assert_eq!((4, 5, 6), (c.x(), c.y(), c.z()));
+    assert_eq!(4, (a - b).y());
+
+    let waypoint = Vector2::new(10, -4);
+    assert_eq!(Vector2::new(4, 10), waypoint.rotate(90)?);
+    assert_eq!(Vector2::new(-4, -10), waypoint.rotate(-90)?);
+    assert_eq!(Vector2::new(-4, -10), waypoint.rotate(270)?);
+    assert_eq!(waypoint, waypoint.rotate(720)?);
+    assert!(waypoint.rotate(45).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_directions() -> Result<()> {
+    assert_eq!(Direction::East, Direction::North.right());
+    assert_eq!(Direction::West, Direction::North.left());
+    assert_eq!(Direction::South, Direction::North.reverse());
+    assert_eq!(Direction::West, Direction::South.rotate(90)?);
+    assert_eq!(Direction::East, Direction::South.rotate(-90)?);
+    assert_eq!(Direction::North, Direction::South.rotate(-540)?);
+    assert_eq!(
+        Direction::East.vector(),
+        Direction::North.vector().rotate(90)?
+    );
+
+    assert_eq!(Direction::North, "U".parse()?);
+    assert_eq!(Direction::West, "W".parse()?);
+    assert_eq!(Direction::West, Direction::try_from('L')?);
+    assert!("X".parse::<Direction>().is_err());
+    assert!("NE".parse::<Direction>().is_err());
+    Ok(())
+}
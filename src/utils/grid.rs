@@ -0,0 +1,348 @@
+use crate::utils::{span, Input};
+use anyhow::{bail, Result};
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+/// Offsets to the four orthogonal neighbours: up, right, down, left
+pub const NEIGHBOURS4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets to the eight neighbours, including diagonals, clockwise from up
+pub const NEIGHBOURS8: [(isize, isize); 8] = [
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+    (-1, 0),
+    (-1, -1),
+];
+
+/// Two-dimensional grid stored row by row in a single vector. Positions are `(x, y)` pairs,
+/// `x` being the column and `y` the row, with `(0, 0)` at the top left.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid where all cells have the same value
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Swaps rows and columns
+    pub fn transpose(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Rotates the grid by 90° clockwise
+    pub fn rotate_right(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|x| self.column(x).rev().cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+
+    /// Rotates the grid by 90° counter-clockwise
+    pub fn rotate_left(&self) -> Self {
+        let cells = (0..self.width)
+            .rev()
+            .flat_map(|x| self.column(x).cloned())
+            .collect();
+        Self {
+            width: self.height,
+            height: self.width,
+            cells,
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, which must all have the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (y, row) in rows.into_iter().enumerate() {
+            if row.len() != width {
+                bail!("row {} has {} cells, expected {}", y, row.len(), width)
+            }
+            cells.extend(row);
+        }
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Parses each char of the non-empty input lines into a cell
+    pub fn parse<F: FnMut(char) -> Result<T>>(input: &Input, mut parse: F) -> Result<Self> {
+        let mut rows: Vec<Vec<T>> = Vec::new();
+        for (y, line) in input.lines().enumerate().filter(|(_, l)| !l.is_empty()) {
+            let mut row = Vec::with_capacity(line.len());
+            for (x, c) in line.chars().enumerate() {
+                match parse(c) {
+                    Ok(cell) => row.push(cell),
+                    Err(err) => {
+                        let message = format!("{:#}", err);
+                        return Err(input.error(y, span(x..x + 1, message)).into());
+                    }
+                }
+            }
+            if let Some(width) = rows.first().map(Vec::len).filter(|w| *w != row.len()) {
+                let message = format!("row has {} cells, expected {}", row.len(), width);
+                let chars = width.min(row.len())..row.len().max(width);
+                return Err(input.error(y, span(chars, message)).into());
+            }
+            rows.push(row);
+        }
+        Self::from_rows(rows)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a position is inside the grid
+    pub fn contains(&self, x: isize, y: isize) -> bool {
+        x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height
+    }
+
+    /// Returns a cell, or None if the position is outside the grid
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        match self.contains(x, y) {
+            true => Some(&self.cells[y as usize * self.width + x as usize]),
+            false => None,
+        }
+    }
+
+    /// Returns a mutable cell, or None if the position is outside the grid
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        match self.contains(x, y) {
+            true => Some(&mut self.cells[y as usize * self.width + x as usize]),
+            false => None,
+        }
+    }
+
+    /// Returns a cell of the grid repeated infinitely in all directions, None if the grid
+    /// is empty
+    pub fn get_wrapping(&self, x: isize, y: isize) -> Option<&T> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        let x = x.rem_euclid(self.width as isize) as usize;
+        let y = y.rem_euclid(self.height as isize) as usize;
+        Some(&self.cells[y * self.width + x])
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells.iter().skip(x).step_by(self.width.max(1))
+    }
+
+    /// All cells, row by row
+    pub fn cells(&self) -> &[T] {
+        &self.cells
+    }
+
+    pub fn cells_mut(&mut self) -> &mut [T] {
+        &mut self.cells
+    }
+
+    /// Iterates on the cells, row by row
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.cells.iter_mut()
+    }
+
+    /// Iterates on all positions, row by row
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates on the positions and cells, row by row
+    pub fn enumerate(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Positions of the cells at the given offsets of a position, that are inside the grid
+    pub fn neighbours<'a>(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |(dx, dy)| {
+            let x = x.checked_add_signed(*dx).filter(|x| *x < width)?;
+            let y = y.checked_add_signed(*dy).filter(|y| *y < height)?;
+            Some((x, y))
+        })
+    }
+
+    /// Positions of the orthogonal neighbours of a cell
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &NEIGHBOURS4)
+    }
+
+    /// Positions of the orthogonal and diagonal neighbours of a cell
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        self.neighbours(x, y, &NEIGHBOURS8)
+    }
+
+    /// Positions seen from a cell when looking in a direction, until the edge of the grid.
+    /// The starting cell is not included.
+    pub fn ray(
+        &self,
+        x: usize,
+        y: usize,
+        direction: (isize, isize),
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        let mut position = Some((x, y));
+        std::iter::from_fn(move || {
+            let (x, y) = position?;
+            let x = x.checked_add_signed(direction.0).filter(|x| *x < width);
+            let y = y.checked_add_signed(direction.1).filter(|y| *y < height);
+            position = x.zip(y);
+            position
+        })
+    }
+
+    /// Applies a function on every cell to build a new grid
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// Parses a grid of single digits
+    pub fn parse_digits(input: &Input) -> Result<Self> {
+        Self::parse(input, |c| match c.to_digit(10) {
+            Some(digit) => Ok(digit as u8),
+            None => bail!("invalid digit {}", c),
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width, "column {} out of the grid", x);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width, "column {} out of the grid", x);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Displays the cells row by row, one line per row
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_grid() -> Result<()> {
+    let grid = Grid::parse(&Input::from("abc\ndef\n"), Ok)?;
+    assert_eq!((3, 2), (grid.width(), grid.height()));
+    assert_eq!(Some(&'f'), grid.get(2, 1));
+    assert_eq!(None, grid.get(3, 1));
+    assert_eq!(None, grid.get(-1, 0));
+    assert_eq!('e', grid[(1, 1)]);
+    assert_eq!(Some(&'c'), grid.get_wrapping(-1, 2));
+    assert_eq!(Some(&'a'), grid.get_wrapping(6, -4));
+    assert_eq!(None, Grid::new(0, 3, 'a').get_wrapping(1, 1));
+    assert_eq!(None, Grid::new(2, 0, 'a').get_wrapping(1, 1));
+    assert_eq!(['d', 'e', 'f'], grid.row(1));
+    assert_eq!("be", grid.column(1).collect::<String>());
+
+    let neighbours: Vec<_> = grid.neighbours4(0, 0).collect();
+    assert_eq!(vec![(1, 0), (0, 1)], neighbours);
+    assert_eq!(5, grid.neighbours8(1, 0).count());
+    let ray: Vec<_> = grid.ray(0, 0, (1, 1)).collect();
+    assert_eq!(vec![(1, 1)], ray);
+    let ray: Vec<_> = grid.ray(2, 1, (-1, 0)).collect();
+    assert_eq!(vec![(1, 1), (0, 1)], ray);
+
+    assert_eq!("abc\ndef\n", grid.to_string());
+    assert_eq!("ad\nbe\ncf\n", grid.transpose().to_string());
+    assert_eq!("da\neb\nfc\n", grid.rotate_right().to_string());
+    assert_eq!("cf\nbe\nad\n", grid.rotate_left().to_string());
+    assert_eq!(
+        grid,
+        grid.rotate_right()
+            .rotate_right()
+            .rotate_left()
+            .rotate_left()
+    );
+
+    let digits = Grid::parse_digits(&Input::from("12\n34"))?;
+    assert_eq!(10, digits.iter().map(|d| *d as usize).sum::<usize>());
+    assert_eq!(
+        "2468",
+        digits
+            .map(|d| d * 2)
+            .iter()
+            .map(u8::to_string)
+            .collect::<String>()
+    );
+    let error = |input| {
+        Grid::parse_digits(&Input::from(input))
+            .unwrap_err()
+            .to_string()
+    };
+    assert!(error("12\n3").starts_with("line 2, column 2: row has 1 cells, expected 2"));
+    assert!(error("12\n1a").ends_with("2 | 1a\n  |  ^"));
+    Ok(())
+}
@@ -0,0 +1,322 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Range, RangeInclusive};
+
+/// Range of integers from `start` included to `end` excluded, empty when `end <= start`
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    /// Interval with an exclusive end
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// Interval with an inclusive end
+    pub const fn inclusive(first: i64, last: i64) -> Self {
+        Self::new(first, last + 1)
+    }
+
+    /// Last value of the interval
+    pub fn last(&self) -> i64 {
+        self.end - 1
+    }
+
+    pub fn len(&self) -> u64 {
+        self.end.saturating_sub(self.start).max(0) as u64
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether all values of `other` are in this interval, an empty interval being covered
+    pub fn covers(&self, other: &Interval) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    /// Whether the intervals have at least one value in common
+    pub fn overlaps(&self, other: &Interval) -> bool {
+        !self.intersection(other).is_empty()
+    }
+
+    /// Values in both intervals, possibly empty
+    pub fn intersection(&self, other: &Interval) -> Interval {
+        Self::new(self.start.max(other.start), self.end.min(other.end))
+    }
+}
+
+impl From<Range<i64>> for Interval {
+    fn from(range: Range<i64>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<RangeInclusive<i64>> for Interval {
+    fn from(range: RangeInclusive<i64>) -> Self {
+        Self::inclusive(*range.start(), *range.end())
+    }
+}
+
+/// Displays the interval in Rust notation, like `3..7`
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Set of integers stored as sorted, disjoint and non-adjacent intervals
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RangeSet {
+    intervals: Vec<Interval>,
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds all values of an interval, merging the intervals it touches
+    pub fn insert(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        // Intervals before `first` end before the new one, intervals from `last` start after
+        let first = self.intervals.partition_point(|i| i.end < interval.start);
+        let last = self.intervals.partition_point(|i| i.start <= interval.end);
+        let mut merged = interval;
+        if first < last {
+            merged.start = merged.start.min(self.intervals[first].start);
+            merged.end = merged.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    /// Removes all values of an interval, splitting the interval containing it if needed
+    pub fn remove(&mut self, interval: Interval) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.intervals.partition_point(|i| i.end <= interval.start);
+        let last = self.intervals.partition_point(|i| i.start < interval.end);
+        if first == last {
+            return;
+        }
+        let before = Interval::new(self.intervals[first].start, interval.start);
+        let after = Interval::new(interval.end, self.intervals[last - 1].end);
+        let kept = [before, after].into_iter().filter(|i| !i.is_empty());
+        self.intervals.splice(first..last, kept);
+    }
+
+    /// Values in either set
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        let mut union = self.clone();
+        other.iter().for_each(|i| union.insert(*i));
+        union
+    }
+
+    /// Values in both sets
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut intervals = Vec::new();
+        let (mut a, mut b) = (self.intervals.iter(), other.intervals.iter());
+        let (mut left, mut right) = (a.next(), b.next());
+        while let (Some(l), Some(r)) = (left, right) {
+            let common = l.intersection(r);
+            if !common.is_empty() {
+                intervals.push(common);
+            }
+            // Move forward in the set whose interval ends first
+            if l.end < r.end {
+                left = a.next();
+            } else {
+                right = b.next();
+            }
+        }
+        RangeSet { intervals }
+    }
+
+    /// Values in this set but not in the other
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        let mut difference = self.clone();
+        other.iter().for_each(|i| difference.remove(*i));
+        difference
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= value);
+        self.intervals.get(index).is_some_and(|i| i.contains(value))
+    }
+
+    /// Whether all values of an interval are in the set
+    pub fn covers(&self, interval: &Interval) -> bool {
+        let index = self.intervals.partition_point(|i| i.end <= interval.start);
+        interval.is_empty()
+            || self
+                .intervals
+                .get(index)
+                .is_some_and(|i| i.covers(interval))
+    }
+
+    /// Number of values in the set
+    pub fn len(&self) -> u64 {
+        self.intervals.iter().map(Interval::len).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Iterates on the intervals, in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = &Interval> {
+        self.intervals.iter()
+    }
+}
+
+impl FromIterator<Interval> for RangeSet {
+    fn from_iter<I: IntoIterator<Item = Interval>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        iter.into_iter().for_each(|i| set.insert(i));
+        set
+    }
+}
+
+/// Box in an N-dimensional space, made of one interval per axis
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cuboid<const N: usize>(pub [Interval; N]);
+
+impl<const N: usize> Cuboid<N> {
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().any(Interval::is_empty)
+    }
+
+    /// Number of integer points in the box
+    pub fn volume(&self) -> u64 {
+        self.0.iter().map(Interval::len).product()
+    }
+
+    pub fn contains(&self, point: [i64; N]) -> bool {
+        self.0.iter().zip(point).all(|(i, p)| i.contains(p))
+    }
+
+    /// Whether all points of `other` are in this box
+    pub fn covers(&self, other: &Cuboid<N>) -> bool {
+        other.is_empty() || self.0.iter().zip(&other.0).all(|(a, b)| a.covers(b))
+    }
+
+    /// Points in both boxes, None if there is none
+    pub fn intersection(&self, other: &Cuboid<N>) -> Option<Cuboid<N>> {
+        let mut intersection = *self;
+        for (axis, interval) in intersection.0.iter_mut().enumerate() {
+            *interval = interval.intersection(&other.0[axis]);
+        }
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Splits the points of this box that are not in `other` into at most `2 * N` boxes
+    pub fn subtract(&self, other: &Cuboid<N>) -> Vec<Cuboid<N>> {
+        let Some(common) = self.intersection(other) else {
+            return match self.is_empty() {
+                true => vec![],
+                false => vec![*self],
+            };
+        };
+        // Slice off the parts before and after the common box, one axis at a time
+        let mut pieces = Vec::with_capacity(2 * N);
+        let mut rest = *self;
+        for axis in 0..N {
+            let interval = rest.0[axis];
+            for slice in [
+                Interval::new(interval.start, common.0[axis].start),
+                Interval::new(common.0[axis].end, interval.end),
+            ] {
+                if !slice.is_empty() {
+                    let mut piece = rest;
+                    piece.0[axis] = slice;
+                    pieces.push(piece);
+                }
+            }
+            rest.0[axis] = common.0[axis];
+        }
+        pieces
+    }
+}
+
+#[test]
+fn test_intervals() {
+    let a = Interval::from(2..=5);
+    assert_eq!(Interval::new(2, 6), a);
+    assert_eq!((4, 5), (a.len(), a.last()));
+    assert!(a.contains(5) && !a.contains(6));
+    assert!(a.covers(&Interval::new(3, 5)));
+    assert!(!a.covers(&Interval::new(3, 7)));
+    assert!(a.overlaps(&Interval::new(5, 9)));
+    assert!(!a.overlaps(&Interval::new(6, 9)));
+    assert_eq!(0, Interval::new(4, 1).len());
+    assert_eq!("2..6", a.to_string());
+}
+
+#[test]
+fn test_range_set() {
+    let set: RangeSet = [(1..3), (10..15), (3..5), (7..8)]
+        .into_iter()
+        .map(Interval::from)
+        .collect();
+    let intervals: Vec<String> = set.iter().map(Interval::to_string).collect();
+    assert_eq!(vec!["1..5", "7..8", "10..15"], intervals);
+    assert_eq!(10, set.len());
+    assert!(set.contains(4) && !set.contains(5) && set.contains(14));
+    assert!(set.covers(&Interval::new(11, 15)));
+    assert!(!set.covers(&Interval::new(4, 8)));
+
+    let mut other = RangeSet::new();
+    other.insert(Interval::new(4, 11));
+    let show = |set: RangeSet| set.iter().map(|i| i.to_string()).collect::<Vec<_>>();
+    assert_eq!(vec!["1..15"], show(set.union(&other)));
+    assert_eq!(
+        vec!["4..5", "7..8", "10..11"],
+        show(set.intersection(&other))
+    );
+    assert_eq!(vec!["1..4", "11..15"], show(set.difference(&other)));
+    assert_eq!(vec!["5..7", "8..10"], show(other.difference(&set)));
+
+    let mut set = set;
+    set.remove(Interval::new(12, 13));
+    set.remove(Interval::new(0, 2));
+    assert_eq!(vec!["2..5", "7..8", "10..12", "13..15"], show(set));
+}
+
+#[test]
+fn test_cuboids() {
+    let cube = |x: i64, y: i64, z: i64, size: i64| {
+        Cuboid([
+            Interval::new(x, x + size),
+            Interval::new(y, y + size),
+            Interval::new(z, z + size),
+        ])
+    };
+    let big = cube(0, 0, 0, 3);
+    let small = cube(1, 1, 1, 1);
+    assert_eq!(27, big.volume());
+    assert!(big.covers(&small) && !small.covers(&big));
+    assert_eq!(Some(small), big.intersection(&small));
+    assert_eq!(None, big.intersection(&cube(3, 0, 0, 1)));
+
+    // Carving the center out of the cube leaves 6 boxes
+    let pieces = big.subtract(&small);
+    assert_eq!(6, pieces.len());
+    assert_eq!(26, pieces.iter().map(Cuboid::volume).sum::<u64>());
+    assert!(pieces.iter().all(|p| p.intersection(&small).is_none()));
+
+    // Overlapping on a corner
+    let pieces = big.subtract(&cube(2, 2, 2, 5));
+    assert_eq!(3, pieces.len());
+    assert_eq!(26, pieces.iter().map(Cuboid::volume).sum::<u64>());
+    assert_eq!(vec![small], small.subtract(&cube(5, 5, 5, 1)));
+    assert!(small.subtract(&big).is_empty());
+}
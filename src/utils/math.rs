@@ -0,0 +1,138 @@
+use anyhow::{bail, Context, Result};
+
+/// Greatest common divisor, always positive unless both numbers are 0. Unsigned, as the gcd
+/// of `i64::MIN` and 0 does not fit in an i64.
+pub fn gcd(a: i64, b: i64) -> u64 {
+    let (mut a, mut b) = (a.unsigned_abs(), b.unsigned_abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, None on overflow
+pub fn lcm(a: i64, b: i64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a.unsigned_abs() / gcd(a, b)).checked_mul(b.unsigned_abs())
+}
+
+/// Extended Euclidean algorithm, returns `(g, x, y)` such that `a * x + b * y = g`,
+/// `g` being the gcd of `a` and `b`
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (1, 0);
+    let (mut y0, mut y1) = (0, 1);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    match r0 < 0 {
+        true => (-r0, -x0, -y0),
+        false => (r0, x0, y0),
+    }
+}
+
+/// Inverse of `a` modulo `m`, None if they are not coprime or if `m` is not positive
+pub fn mod_inverse(a: i64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.rem_euclid(m), m);
+    (g == 1).then(|| x.rem_euclid(m))
+}
+
+/// Computes `base ^ exp % m` by repeated squaring, None if `m` is not positive
+pub fn mod_pow(base: i64, mut exp: u64, m: i64) -> Option<i64> {
+    if m <= 0 {
+        return None;
+    }
+    let m = m as i128;
+    let mut base = (base as i128).rem_euclid(m);
+    let mut result = 1 % m;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = result * base % m;
+        }
+        base = base * base % m;
+        exp >>= 1;
+    }
+    Some(result as i64)
+}
+
+/// Chinese Remainder Theorem: solves the system of congruences `x = r (mod m)`, given as
+/// `(r, m)` pairs. Moduli need not be coprime. Returns `(x, m)` where `m` is the lcm of the
+/// moduli and `x` the smallest non-negative solution, or an error if there is none or if `m`
+/// overflows.
+pub fn crt(congruences: &[(i64, i64)]) -> Result<(i64, i64)> {
+    let mut solution: (i64, i64) = (0, 1);
+    for &(r, m) in congruences {
+        if m <= 0 {
+            bail!("invalid modulus {}", m)
+        }
+        let (r1, m1) = solution;
+        let r2 = r.rem_euclid(m);
+        // Both moduli are positive, their gcd fits in an i64
+        let g = gcd(m1, m) as i64;
+        if (r2 - r1) % g != 0 {
+            bail!(
+                "x = {} (mod {}) is incompatible with x = {} (mod {})",
+                r1,
+                m1,
+                r,
+                m
+            )
+        }
+        let modulus = lcm(m1, m)
+            .and_then(|modulus| i64::try_from(modulus).ok())
+            .with_context(|| format!("lcm of {} and {} overflows", m1, m))?;
+        // x = r1 + m1 * k where k = (r2 - r1) / g * inverse(m1 / g) (mod m / g)
+        // m1 / g and m / g are coprime, the inverse always exists
+        let inverse = mod_inverse(m1 / g, m / g).unwrap_or(0) as i128;
+        let k = ((r2 - r1) / g) as i128 * inverse % (m / g) as i128;
+        let x = (r1 as i128 + m1 as i128 * k).rem_euclid(modulus as i128);
+        solution = (x as i64, modulus);
+    }
+    Ok(solution)
+}
+
+#[test]
+fn test_math() -> Result<()> {
+    assert_eq!(6, gcd(12, -18));
+    assert_eq!(5, gcd(0, 5));
+    assert_eq!(Some(36), lcm(12, 18));
+    assert_eq!(Some(u64::MAX - 1), lcm(i64::MAX, 2));
+    assert_eq!(None, lcm(i64::MAX, 3));
+    assert_eq!(1 << 63, gcd(i64::MIN, 0));
+    assert_eq!(1 << 63, gcd(i64::MIN, i64::MIN));
+    assert_eq!(1, gcd(i64::MIN, i64::MAX));
+    assert_eq!(Some(1 << 63), lcm(i64::MIN, 2));
+    assert_eq!(None, lcm(i64::MIN, 3));
+
+    let (g, x, y) = extended_gcd(240, 46);
+    assert_eq!(2, g);
+    assert_eq!(2, 240 * x + 46 * y);
+    assert_eq!(Some(4), mod_inverse(3, 11));
+    assert_eq!(Some(7), mod_inverse(-3, 11));
+    assert_eq!(None, mod_inverse(6, 9));
+    assert_eq!(None, mod_inverse(3, 0));
+    assert_eq!(None, mod_inverse(3, -11));
+    assert_eq!(Some(445), mod_pow(4, 13, 497));
+    assert_eq!(Some(0), mod_pow(5, 0, 1));
+    assert_eq!(None, mod_pow(5, 2, 0));
+    assert_eq!(None, mod_pow(5, 2, -3));
+    // Fermat's little theorem with the prime 2^61 - 1, where products overflow i64
+    let p = (1 << 61) - 1;
+    assert_eq!(Some(1), mod_pow(3, p as u64 - 1, p));
+
+    assert_eq!((23, 105), crt(&[(2, 3), (3, 5), (2, 7)])?);
+    assert_eq!((10, 12), crt(&[(4, 6), (2, 4)])?);
+    assert_eq!((3, 4), crt(&[(-1, 4), (1, 2)])?);
+    assert!(crt(&[(1, 6), (2, 4)]).is_err());
+    assert!(crt(&[(1, 0)]).is_err());
+    assert!(crt(&[(0, i64::MAX), (0, i64::MAX - 1)]).is_err());
+    Ok(())
+}
@@ -1,5 +1,29 @@
+mod answers;
+mod automaton;
+mod cycle;
+mod examples;
+mod geometry;
+mod grid;
+mod intervals;
+mod math;
+mod ocr;
 mod parsing;
+mod search;
+mod solution;
 mod testing;
+mod timing;
 
+pub use answers::*;
+pub use automaton::*;
+pub use cycle::*;
+pub use examples::*;
+pub use geometry::*;
+pub use grid::*;
+pub use intervals::*;
+pub use math::*;
+pub use ocr::*;
 pub use parsing::*;
+pub use search::*;
+pub use solution::*;
 pub use testing::*;
+pub use timing::*;
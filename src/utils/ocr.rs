@@ -0,0 +1,73 @@
+use anyhow::{bail, Result};
+
+const LETTER_WIDTH: usize = 4;
+const LETTER_HEIGHT: usize = 6;
+
+/// Known glyphs of the puzzle font, six rows of four pixels each. Y is five pixels wide and
+/// cannot be read with the others.
+const GLYPHS: [(char, [&str; LETTER_HEIGHT]); 17] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Reads the capital letters drawn by some puzzles, where `#` is a lit pixel and any other
+/// char is dark. Letters are four pixels wide, separated by one dark column.
+pub fn read_letters(drawing: &str) -> Result<String> {
+    let rows: Vec<Vec<bool>> = drawing
+        .lines()
+        .skip_while(|l| !l.contains('#'))
+        .take(LETTER_HEIGHT)
+        .map(|l| l.chars().map(|c| c == '#').collect())
+        .collect();
+    if rows.len() != LETTER_HEIGHT {
+        bail!("expected {} rows of pixels", LETTER_HEIGHT)
+    }
+
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let lit = |x: usize, y: usize| rows[y].get(x).copied().unwrap_or(false);
+    let mut letters = String::new();
+    for start in (0..width).step_by(LETTER_WIDTH + 1) {
+        let glyph = GLYPHS.iter().find(|(_, pixels)| {
+            pixels.iter().enumerate().all(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .all(|(x, p)| (p == '#') == lit(start + x, y))
+            })
+        });
+        match glyph {
+            Some((letter, _)) => letters.push(*letter),
+            None => bail!("unknown letter at column {}", start),
+        }
+    }
+    Ok(letters)
+}
+
+#[test]
+fn test_read_letters() -> Result<()> {
+    let drawing = "
+###  #  #  ##  #    ###   ##  ###   ##
+#  # #  # #  # #    #  # #  # #  # #  #
+#  # #### #  # #    #  # #    #  # #  #
+###  #  # #### #    ###  #    ###  ####
+# #  #  # #  # #    # #  #  # # #  #  #
+#  # #  # #  # #### #  #  ##  #  # #  #
+";
+    assert_eq!("RHALRCRA", read_letters(drawing)?);
+    assert!(read_letters("#\n#\n").is_err());
+    Ok(())
+}
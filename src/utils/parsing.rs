@@ -1,6 +1,81 @@
-use anyhow::{bail, Result};
-use regex::Regex;
+use anyhow::{anyhow, bail, Result};
+use regex::{Captures, Regex};
+use std::fmt::{Display, Formatter};
+use std::ops::Range;
 use std::str::FromStr;
+use std::sync::OnceLock;
+use thiserror::Error;
+
+/// Error parsing a line of the input, locating the offending text
+#[derive(Debug, Error)]
+pub struct ParseError {
+    /// Day whose input failed to parse, like `y2020::d05`
+    pub day: Option<String>,
+    /// Line number, from 1
+    pub line: usize,
+    /// Columns of the offending text, from 1, the end being excluded
+    pub columns: Range<usize>,
+    /// Content of the line
+    pub text: String,
+    pub message: String,
+}
+
+/// Error locating the part of a string that failed to parse, as a range of chars. Parsing
+/// helpers of `Input` use it to point at the offending text instead of the whole line.
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct SpanError {
+    pub chars: Range<usize>,
+    pub message: String,
+}
+
+impl ParseError {
+    /// Wraps the error of parsing a line, `line` counting from 0
+    pub fn new(day: Option<&str>, line: usize, text: &str, error: anyhow::Error) -> Self {
+        let columns = match error.downcast_ref::<SpanError>() {
+            Some(span) => span.chars.start + 1..span.chars.end.max(span.chars.start + 1) + 1,
+            None => 1..text.chars().count().max(1) + 1,
+        };
+        Self {
+            day: day.map(str::to_owned),
+            line: line + 1,
+            columns,
+            text: text.to_owned(),
+            message: format!("{:#}", error),
+        }
+    }
+}
+
+/// Shows the error with the line and a caret under the offending text:
+/// ```text
+/// y2020::d02 line 3, column 2: expected "-" at column 2 in "1+3 a: abcde"
+///   |
+/// 3 | 1+3 a: abcde
+///   |  ^
+/// ```
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(day) = &self.day {
+            write!(f, "{} ", day)?;
+        }
+        write!(f, "line {}, ", self.line)?;
+        match self.columns.len() {
+            1 => write!(f, "column {}", self.columns.start)?,
+            _ => write!(f, "columns {}-{}", self.columns.start, self.columns.end - 1)?,
+        }
+        writeln!(f, ": {}", self.message)?;
+        let margin = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", margin)?;
+        writeln!(f, "{} | {}", self.line, self.text)?;
+        write!(
+            f,
+            "{} | {}{}",
+            margin,
+            " ".repeat(self.columns.start - 1),
+            "^".repeat(self.columns.len())
+        )
+    }
+}
 
 /// Helper trait to work with regexp captures with less boilerplate
 pub trait CaptureParser {
@@ -42,6 +117,228 @@ impl CaptureParser for regex::Captures<'_> {
     }
 }
 
+/// Scans a line with a pattern made of literal text and typed `{placeholders}`, returning the
+/// values as a tuple, or as a struct whose fields are listed in the same order as the
+/// placeholders. The pattern is compiled once, errors give the field and column that failed.
+///
+/// ```
+/// # use adventofcode_rust::scan;
+/// let (min, max, letter, password): (usize, usize, char, String) =
+///     scan!("1-3 a: abcde", "{usize}-{usize} {char}: {str}")?;
+///
+/// struct Move { count: u8, from: u8, to: u8 }
+/// let step = scan!("move 3 from 1 to 2", "move {u8} from {u8} to {u8}" => Move { count, from, to })?;
+/// # Ok::<(), anyhow::Error>(())
+/// ```
+///
+/// Placeholders are unsigned (`{usize}`, `{u32}`...) and signed (`{isize}`, `{i32}`...)
+/// integers, `{char}`, `{word}` for letters, digits and underscores, and `{str}` for any text.
+#[macro_export]
+macro_rules! scan {
+    ($input:expr, $pattern:literal) => {{
+        static PATTERN: std::sync::OnceLock<Result<$crate::utils::Pattern, String>> =
+            std::sync::OnceLock::new();
+        $crate::utils::Pattern::cached(&PATTERN, $pattern).and_then(|p| p.scan($input))
+    }};
+    ($input:expr, $pattern:literal => $name:ident { $($field:ident),+ $(,)? }) => {
+        $crate::scan!($input, $pattern).map(|($($field,)+)| $name { $($field),+ })
+    };
+}
+
+/// Line pattern with typed placeholders, compiled into a regex. See `scan!`.
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+    /// Regexes matching the beginning of the pattern, up to each segment, to locate errors
+    prefixes: Vec<Regex>,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Literal(String),
+    Field(String),
+}
+
+impl Pattern {
+    pub fn new(pattern: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut rest = pattern;
+        while let Some(start) = rest.find('{') {
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => bail!("unclosed placeholder in pattern {:?}", pattern),
+            };
+            if start > 0 {
+                segments.push(Segment::Literal(rest[..start].to_string()));
+            }
+            segments.push(Segment::Field(rest[start + 1..end].to_string()));
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Literal(rest.to_string()));
+        }
+
+        let mut regex = String::from("^");
+        let mut prefixes = Vec::with_capacity(segments.len());
+        for segment in &segments {
+            regex += &match segment {
+                Segment::Literal(text) => regex::escape(text),
+                Segment::Field(kind) => format!("({})", Self::field_regex(kind)?),
+            };
+            prefixes.push(Regex::new(&regex)?);
+        }
+        Ok(Self {
+            regex: Regex::new(&(regex.clone() + "$"))?,
+            prefixes,
+            segments,
+        })
+    }
+
+    /// Gets a pattern compiled by a previous call, used by `scan!` to compile each pattern once
+    #[doc(hidden)]
+    pub fn cached<'a>(
+        cache: &'a OnceLock<Result<Self, String>>,
+        pattern: &str,
+    ) -> Result<&'a Self> {
+        let compiled = cache.get_or_init(|| Self::new(pattern).map_err(|e| e.to_string()));
+        compiled.as_ref().map_err(|err| anyhow!("{}", err))
+    }
+
+    fn field_regex(kind: &str) -> Result<&'static str> {
+        Ok(match kind {
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => r"\d+",
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => r"[-+]?\d+",
+            "char" => ".",
+            "word" => r"\w+",
+            "str" => ".+?",
+            _ => bail!("unknown placeholder {{{}}}", kind),
+        })
+    }
+
+    /// Kinds of the placeholders, in order
+    fn fields(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Field(kind) => Some(kind.as_str()),
+            Segment::Literal(_) => None,
+        })
+    }
+
+    /// Scans a line into a tuple of values, one per placeholder
+    pub fn scan<T: FromCaptures>(&self, line: &str) -> Result<T> {
+        let fields = self.fields().count();
+        if fields != T::FIELDS {
+            bail!("pattern has {} fields, scanning into {}", fields, T::FIELDS)
+        }
+        match self.regex.captures(line) {
+            Some(captures) => T::from_captures(self, line, &captures),
+            None => Err(self.mismatch(line)),
+        }
+    }
+
+    /// Parses the capture of the field at `index`, the error giving the field and its column
+    fn field<T: FromStr>(&self, line: &str, captures: &Captures, index: usize) -> Result<T>
+    where
+        <T as FromStr>::Err: std::error::Error,
+    {
+        captures.parse(index + 1).map_err(|err| {
+            let (start, end) = captures
+                .get(index + 1)
+                .map_or((0, 0), |m| (m.start(), m.end()));
+            let kind = self.fields().nth(index).unwrap_or_default();
+            let message = format!(
+                "field {} {{{}}} at column {} in {:?}: {:#}",
+                index + 1,
+                kind,
+                column(line, start),
+                line,
+                err
+            );
+            span(chars(line, start, end), message)
+        })
+    }
+
+    /// Describes where a line stops matching the pattern
+    fn mismatch(&self, line: &str) -> anyhow::Error {
+        let mut end = 0;
+        for (prefix, segment) in self.prefixes.iter().zip(&self.segments) {
+            match prefix.find(line) {
+                Some(matched) => end = matched.end(),
+                None => return expected(segment, line, end),
+            }
+        }
+        let message = format!(
+            "unexpected {:?} at column {} in {:?}",
+            &line[end..],
+            column(line, end),
+            line
+        );
+        span(chars(line, end, line.len()), message)
+    }
+}
+
+fn expected(segment: &Segment, line: &str, end: usize) -> anyhow::Error {
+    let expected = match segment {
+        Segment::Literal(text) => format!("{:?}", text),
+        Segment::Field(kind) => format!("{{{}}}", kind),
+    };
+    let message = format!(
+        "expected {} at column {} in {:?}",
+        expected,
+        column(line, end),
+        line
+    );
+    span(chars(line, end, end), message)
+}
+
+/// Error locating a message on a range of chars of a line, counting from 0
+pub(crate) fn span<M: Display>(chars: Range<usize>, message: M) -> anyhow::Error {
+    let message = message.to_string();
+    SpanError { chars, message }.into()
+}
+
+/// Range of chars of a byte range of a line
+fn chars(line: &str, start: usize, end: usize) -> Range<usize> {
+    let start_char = column(line, start) - 1;
+    start_char..start_char + line[start..end].chars().count()
+}
+
+/// Column of a byte offset, counting chars from 1
+fn column(line: &str, offset: usize) -> usize {
+    line[..offset].chars().count() + 1
+}
+
+/// Values that can be built from the captures of a `Pattern`
+pub trait FromCaptures: Sized {
+    const FIELDS: usize;
+
+    fn from_captures(pattern: &Pattern, line: &str, captures: &Captures) -> Result<Self>;
+}
+
+macro_rules! impl_from_captures {
+    ($fields:literal: $($type:ident $index:tt),+) => {
+        impl<$($type: FromStr),+> FromCaptures for ($($type,)+)
+        where
+            $(<$type as FromStr>::Err: std::error::Error,)+
+        {
+            const FIELDS: usize = $fields;
+
+            fn from_captures(pattern: &Pattern, line: &str, captures: &Captures) -> Result<Self> {
+                Ok(($(pattern.field::<$type>(line, captures, $index)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_captures!(1: A 0);
+impl_from_captures!(2: A 0, B 1);
+impl_from_captures!(3: A 0, B 1, C 2);
+impl_from_captures!(4: A 0, B 1, C 2, D 3);
+impl_from_captures!(5: A 0, B 1, C 2, D 3, E 4);
+impl_from_captures!(6: A 0, B 1, C 2, D 3, E 4, F 5);
+impl_from_captures!(7: A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+impl_from_captures!(8: A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
 /// Parses a line of ASCII digits into a vector of u8 integers, fails on non-ascii input.
 /// Non-digit ASCII input is not checked for, and will produce garbage.
 pub fn parse_digit_line(line: &str) -> Result<Vec<u8>> {
@@ -50,3 +347,86 @@ pub fn parse_digit_line(line: &str) -> Result<Vec<u8>> {
     }
     Ok(line.as_bytes().iter().map(|n| n - 48).collect())
 }
+
+#[test]
+fn test_scan() -> Result<()> {
+    let (min, max, letter, password): (usize, usize, char, String) =
+        crate::scan!("1-3 a: abcde", "{usize}-{usize} {char}: {str}")?;
+    assert_eq!((1, 3, 'a', "abcde"), (min, max, letter, password.as_str()));
+
+    struct Target {
+        x: (i32, i32),
+        y: i32,
+    }
+    let scan = |line| {
+        crate::scan!(line, "x={i32}..{i32}, y={i32}").map(|(x1, x2, y)| Target { x: (x1, x2), y })
+    };
+    let target = scan("x=20..30, y=-10")?;
+    assert_eq!(((20, 30), -10), (target.x, target.y));
+
+    let error = |result: Result<Target>| format!("{:#}", result.err().unwrap());
+    assert_eq!(
+        "expected \"..\" at column 5 in \"x=20.30, y=-10\"",
+        error(scan("x=20.30, y=-10"))
+    );
+    assert_eq!(
+        "expected {i32} at column 13 in \"x=20..30, y=a\"",
+        error(scan("x=20..30, y=a"))
+    );
+    assert_eq!(
+        "unexpected \"!\" at column 16 in \"x=20..30, y=-10!\"",
+        error(scan("x=20..30, y=-10!"))
+    );
+    assert!(error(scan("x=20..99999999999, y=0"))
+        .starts_with("field 2 {i32} at column 7 in \"x=20..99999999999, y=0\": "));
+
+    struct Move {
+        count: u8,
+        from: u8,
+        to: u8,
+    }
+    let step = crate::scan!("move 3 from 1 to 2", "move {u8} from {u8} to {u8}" => Move { count, from, to })?;
+    assert_eq!((3, 1, 2), (step.count, step.from, step.to));
+
+    let pattern = Pattern::new("{word} {word}")?;
+    assert!(pattern.scan::<(String,)>("a b").is_err());
+    assert!(Pattern::new("{float}").is_err());
+    assert!(Pattern::new("{usize").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parse_error() {
+    let scan = |line| crate::scan!(line, "{usize}-{usize}").map(|(_, _): (u8, u8)| ());
+    let error = ParseError::new(Some("y2020::d02"), 2, "1+3", scan("1+3").unwrap_err());
+    assert_eq!(3, error.line);
+    assert_eq!(2..3, error.columns);
+    assert_eq!(
+        "y2020::d02 line 3, column 2: expected \"-\" at column 2 in \"1+3\"\n  |\n3 | 1+3\n  |  ^",
+        error.to_string()
+    );
+
+    // The example of the Display documentation
+    let line = "1+3 a: abcde";
+    let error = crate::scan!(line, "{usize}-{usize} {char}: {str}")
+        .map(|(_, _, _, _): (usize, usize, char, String)| ())
+        .unwrap_err();
+    let error = ParseError::new(Some("y2020::d02"), 2, line, error);
+    assert_eq!(
+        concat!(
+            "y2020::d02 line 3, column 2: expected \"-\" at column 2 in \"1+3 a: abcde\"\n",
+            "  |\n3 | 1+3 a: abcde\n  |  ^"
+        ),
+        error.to_string()
+    );
+
+    let error = ParseError::new(None, 9, "1-300", scan("1-300").unwrap_err());
+    assert_eq!(3..6, error.columns);
+    assert!(error.to_string().ends_with("\n10 | 1-300\n   |   ^^^"));
+
+    let error = ParseError::new(None, 0, "abc", anyhow!("invalid"));
+    assert_eq!(
+        "line 1, columns 1-3: invalid\n  |\n1 | abc\n  | ^^^",
+        error.to_string()
+    );
+}
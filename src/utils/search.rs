@@ -0,0 +1,302 @@
+use anyhow::{bail, Result};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// Search over an implicit graph, where the successors of a state are computed on demand.
+/// States are expanded at most once, in order of increasing cost, until a goal is reached.
+#[derive(Debug, Clone)]
+pub struct Search<S> {
+    start: S,
+    limit: Option<usize>,
+}
+
+/// Costs of the states reached by a search, and the parent of each state on its best path
+#[derive(Debug, Clone)]
+pub struct Paths<S, C> {
+    costs: HashMap<S, C>,
+    parents: HashMap<S, S>,
+    goal: Option<S>,
+    visited: usize,
+}
+
+impl<S: Clone + Eq + Hash> Search<S> {
+    pub fn new(start: S) -> Self {
+        Self { start, limit: None }
+    }
+
+    /// Stops searching after expanding a number of states
+    pub fn limit(mut self, visits: usize) -> Self {
+        self.limit = Some(visits);
+        self
+    }
+
+    fn limit_reached(&self, visited: usize) -> bool {
+        self.limit.is_some_and(|limit| visited >= limit)
+    }
+
+    /// Breadth-first search, where every move costs 1
+    pub fn bfs<F, I, G>(&self, mut successors: F, mut goal: G) -> Paths<S, usize>
+    where
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = S>,
+        G: FnMut(&S) -> bool,
+    {
+        let mut paths = Paths::new(self.start.clone(), 0);
+        let mut queue = VecDeque::from([self.start.clone()]);
+        while let Some(state) = queue.pop_front() {
+            if goal(&state) {
+                paths.goal = Some(state);
+                break;
+            }
+            if self.limit_reached(paths.visited) {
+                break;
+            }
+            paths.visited += 1;
+            let cost = paths.costs[&state] + 1;
+            for next in successors(&state) {
+                if !paths.costs.contains_key(&next) {
+                    paths.costs.insert(next.clone(), cost);
+                    paths.parents.insert(next.clone(), state.clone());
+                    queue.push_back(next);
+                }
+            }
+        }
+        paths
+    }
+
+    /// Dijkstra's shortest paths, `successors` returning each next state with the cost to move
+    /// there
+    pub fn dijkstra<C, F, I, G>(&self, successors: F, goal: G) -> Paths<S, C>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = (S, C)>,
+        G: FnMut(&S) -> bool,
+    {
+        self.astar(successors, |_| C::default(), goal)
+    }
+
+    /// A* shortest paths, `heuristic` estimating the remaining cost to a goal. The heuristic
+    /// must never overestimate, nor decrease by more than the cost of a move.
+    pub fn astar<C, F, I, H, G>(
+        &self,
+        mut successors: F,
+        mut heuristic: H,
+        mut goal: G,
+    ) -> Paths<S, C>
+    where
+        C: Copy + Ord + Default + Add<Output = C>,
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = (S, C)>,
+        H: FnMut(&S) -> C,
+        G: FnMut(&S) -> bool,
+    {
+        let mut paths = Paths::new(self.start.clone(), C::default());
+        let mut queue = BinaryHeap::from([Queued {
+            priority: heuristic(&self.start),
+            cost: C::default(),
+            state: self.start.clone(),
+        }]);
+        while let Some(Queued { cost, state, .. }) = queue.pop() {
+            // Stale entries remain in the queue when a cheaper path is found
+            if cost > paths.costs[&state] {
+                continue;
+            }
+            if goal(&state) {
+                paths.goal = Some(state);
+                break;
+            }
+            if self.limit_reached(paths.visited) {
+                break;
+            }
+            paths.visited += 1;
+            for (next, step) in successors(&state) {
+                let cost = cost + step;
+                if paths.costs.get(&next).is_none_or(|c| cost < *c) {
+                    paths.costs.insert(next.clone(), cost);
+                    paths.parents.insert(next.clone(), state.clone());
+                    queue.push(Queued {
+                        priority: cost + heuristic(&next),
+                        cost,
+                        state: next,
+                    });
+                }
+            }
+        }
+        paths
+    }
+
+    /// Counts the distinct paths from the start to any goal, a path ending at the first goal it
+    /// reaches. The graph of states must not have cycles, which would make the count infinite.
+    pub fn count_paths<F, I, G>(&self, mut successors: F, mut goal: G) -> Result<usize>
+    where
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = S>,
+        G: FnMut(&S) -> bool,
+    {
+        let mut counts = HashMap::new();
+        let mut on_path = HashSet::new();
+        self.count_from(
+            &self.start,
+            &mut successors,
+            &mut goal,
+            &mut counts,
+            &mut on_path,
+        )
+    }
+
+    /// Counts the paths from a state, memoizing the count of every expanded state
+    fn count_from<F, I, G>(
+        &self,
+        state: &S,
+        successors: &mut F,
+        goal: &mut G,
+        counts: &mut HashMap<S, usize>,
+        on_path: &mut HashSet<S>,
+    ) -> Result<usize>
+    where
+        F: FnMut(&S) -> I,
+        I: IntoIterator<Item = S>,
+        G: FnMut(&S) -> bool,
+    {
+        if goal(state) {
+            return Ok(1);
+        }
+        if let Some(count) = counts.get(state) {
+            return Ok(*count);
+        }
+        if self.limit_reached(counts.len() + on_path.len()) {
+            bail!("still counting paths after {} states", counts.len())
+        }
+        if !on_path.insert(state.clone()) {
+            bail!("cycle in the states, the number of paths is infinite")
+        }
+        let mut count = 0;
+        for next in successors(state) {
+            count += self.count_from(&next, successors, goal, counts, on_path)?;
+        }
+        on_path.remove(state);
+        counts.insert(state.clone(), count);
+        Ok(count)
+    }
+}
+
+impl<S: Clone + Eq + Hash, C: Copy> Paths<S, C> {
+    fn new(start: S, zero: C) -> Self {
+        Self {
+            costs: HashMap::from([(start, zero)]),
+            parents: HashMap::new(),
+            goal: None,
+            visited: 0,
+        }
+    }
+
+    /// The goal reached by the search, if any
+    pub fn goal(&self) -> Option<&S> {
+        self.goal.as_ref()
+    }
+
+    /// Cost of the best path to the goal
+    pub fn goal_cost(&self) -> Option<C> {
+        self.goal.as_ref().and_then(|goal| self.cost(goal))
+    }
+
+    /// Lowest known cost to reach a state. Only the costs of the expanded states are final
+    /// when the search stopped early.
+    pub fn cost(&self, state: &S) -> Option<C> {
+        self.costs.get(state).copied()
+    }
+
+    /// All the states reached with their lowest known cost
+    pub fn costs(&self) -> &HashMap<S, C> {
+        &self.costs
+    }
+
+    /// Number of states expanded by the search
+    pub fn visited(&self) -> usize {
+        self.visited
+    }
+
+    /// Path from the start to a state, both included
+    pub fn path(&self, state: &S) -> Option<Vec<S>> {
+        if !self.costs.contains_key(state) {
+            return None;
+        }
+        let mut path = vec![state.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Path from the start to the goal
+    pub fn goal_path(&self) -> Option<Vec<S>> {
+        self.goal.as_ref().and_then(|goal| self.path(goal))
+    }
+}
+
+/// State waiting in the priority queue, popped by lowest priority first
+struct Queued<S, C> {
+    priority: C,
+    cost: C,
+    state: S,
+}
+
+impl<S, C: Ord> Ord for Queued<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+impl<S, C: Ord> PartialOrd for Queued<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S, C: Ord> PartialEq for Queued<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<S, C: Ord> Eq for Queued<S, C> {}
+
+#[test]
+fn test_search() -> Result<()> {
+    // Positions on a line, moving by +1 or +3 with costs 1 and 5
+    let moves = |n: &i32| [(n + 1, 1), (n + 3, 5)];
+    let steps = |n: &i32| [n + 1, n + 3];
+
+    let paths = Search::new(0).bfs(steps, |n| *n == 7);
+    assert_eq!(Some(&7), paths.goal());
+    assert_eq!(Some(3), paths.goal_cost());
+    assert_eq!(Some(vec![0, 1, 4, 7]), paths.goal_path());
+
+    let paths = Search::new(0).dijkstra(moves, |n| *n == 7);
+    assert_eq!(Some(7), paths.goal_cost());
+    assert_eq!(Some(vec![0, 1, 2, 3, 4, 5, 6, 7]), paths.goal_path());
+    let heuristic = |n: &i32| (7 - n).max(0);
+    let astar = Search::new(0).astar(moves, heuristic, |n| *n == 7);
+    assert_eq!(Some(7), astar.goal_cost());
+    assert!(astar.visited() <= paths.visited());
+
+    let limited = Search::new(0).limit(3).bfs(steps, |n| *n == 100);
+    assert_eq!(None, limited.goal());
+    assert_eq!(3, limited.visited());
+    assert_eq!(Some(vec![0, 3, 6]), limited.path(&6));
+    assert_eq!(None, limited.path(&100));
+
+    // Compositions of 5 with parts 1 and 3: 1+1+1+1+1, 1+1+3, 1+3+1, 3+1+1
+    let search = Search::new(0);
+    assert_eq!(
+        4,
+        search.count_paths(|n| steps(n).into_iter().filter(|n| *n <= 5), |n| *n == 5)?
+    );
+    assert_eq!(0, search.count_paths(|_| [], |n| *n == 5)?);
+    assert!(search.count_paths(|n| [(n + 1) % 3], |n| *n == 5).is_err());
+    Ok(())
+}
@@ -0,0 +1,228 @@
+use crate::utils::{Input, DEFAULT_PROFILE};
+use anyhow::{bail, Result};
+use std::any::{type_name, Any};
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+/// The answer to one part of a puzzle
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Answer {
+    Integer(i64),
+    /// Integers that do not fit in an i64, mostly products of large numbers
+    BigInteger(i128),
+    Text(String),
+    /// The part is not solved yet
+    Unsolved,
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Integer(value) => write!(f, "{}", value),
+            Answer::BigInteger(value) => write!(f, "{}", value),
+            Answer::Text(value) => write!(f, "{}", value),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+macro_rules! impl_answer_from {
+    ($($int:ty),+) => {
+    $(
+        impl From<$int> for Answer {
+            fn from(value: $int) -> Self {
+                match i64::try_from(value) {
+                    Ok(value) => Answer::Integer(value),
+                    Err(_) => Answer::BigInteger(value as i128),
+                }
+            }
+        }
+    )*
+    }
+}
+
+impl_answer_from!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, i128);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// A puzzle solution, identified by its year, day and optional variant name.
+/// The input is parsed once, then both parts can be computed independently from it.
+pub trait Solution {
+    /// Puzzle year, for example 2020
+    fn year(&self) -> u16;
+
+    /// Puzzle day, from 1 to 25
+    fn day(&self) -> u8;
+
+    /// Name of the alternative implementation (`opt` for `d05_opt`), None for the main one
+    fn variant(&self) -> Option<&'static str>;
+
+    /// Type names of the answers returned for both parts
+    fn answer_types(&self) -> (&'static str, &'static str);
+
+    /// Parses the input into the representation shared by both parts
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>>;
+
+    /// Computes the first part from the output of `parse`
+    fn part1(&self, parsed: &dyn Any) -> Result<Answer>;
+
+    /// Computes the second part from the output of `parse`
+    fn part2(&self, parsed: &dyn Any) -> Result<Answer>;
+
+    /// Parses the input and computes both parts
+    fn run(&self, input: &Input) -> Result<(Answer, Answer)> {
+        let parsed = self.parse(input)?;
+        Ok((self.part1(parsed.as_ref())?, self.part2(parsed.as_ref())?))
+    }
+
+    /// Module path of the solution, for example `y2020::d05_opt`
+    fn name(&self) -> String {
+        match self.variant() {
+            None => format!("y{}::d{:02}", self.year(), self.day()),
+            Some(variant) => format!("y{}::d{:02}_{}", self.year(), self.day(), variant),
+        }
+    }
+
+    /// Reads the puzzle input for this day from the default profile, shared by all variants
+    fn input(&self) -> Result<Input> {
+        self.input_from(DEFAULT_PROFILE)
+    }
+
+    /// Reads the puzzle input for this day from the given profile
+    fn input_from(&self, profile: &str) -> Result<Input> {
+        let day = format!("y{}::d{:02}", self.year(), self.day());
+        Ok(Input::read_file(self.input_path(profile))?.with_day(&day))
+    }
+
+    /// Location of the puzzle input for this day in the given profile
+    fn input_path(&self, profile: &str) -> PathBuf {
+        Input::path(
+            profile,
+            &format!("y{}", self.year()),
+            &format!("d{:02}", self.day()),
+        )
+    }
+}
+
+/// Wraps the `parse`, `part1` and `part2` functions of a day module to implement `Solution`
+pub struct Day<P, A, B> {
+    year: u16,
+    day: u8,
+    variant: Option<&'static str>,
+    parse: fn(&Input) -> Result<P>,
+    part1: fn(&P) -> Result<A>,
+    part2: fn(&P) -> Result<B>,
+}
+
+impl<P, A, B> Day<P, A, B> {
+    /// Builds a solution from its module names (`y2020`, `d05_opt`) and functions.
+    /// Panics on invalid module names, as they are checked by the registry tests.
+    pub fn new(
+        year: &'static str,
+        module: &'static str,
+        parse: fn(&Input) -> Result<P>,
+        part1: fn(&P) -> Result<A>,
+        part2: fn(&P) -> Result<B>,
+    ) -> Self {
+        let (day, variant) = match module.split_once('_') {
+            None => (module, None),
+            Some((day, variant)) => (day, Some(variant)),
+        };
+        Self {
+            year: parse_module_number(year, 'y'),
+            day: parse_module_number(day, 'd'),
+            variant,
+            parse,
+            part1,
+            part2,
+        }
+    }
+
+    fn downcast<'a>(&self, parsed: &'a dyn Any) -> Result<&'a P>
+    where
+        P: 'static,
+    {
+        match parsed.downcast_ref() {
+            Some(parsed) => Ok(parsed),
+            None => bail!(
+                "unexpected parsed input type, expected {}",
+                type_name::<P>()
+            ),
+        }
+    }
+}
+
+fn parse_module_number<T: std::str::FromStr>(name: &str, prefix: char) -> T {
+    match name.strip_prefix(prefix).map(str::parse) {
+        Some(Ok(value)) => value,
+        _ => panic!("invalid module name {}", name),
+    }
+}
+
+impl<P: 'static, A: Into<Answer>, B: Into<Answer>> Solution for Day<P, A, B> {
+    fn year(&self) -> u16 {
+        self.year
+    }
+
+    fn day(&self) -> u8 {
+        self.day
+    }
+
+    fn variant(&self) -> Option<&'static str> {
+        self.variant
+    }
+
+    fn answer_types(&self) -> (&'static str, &'static str) {
+        (type_name::<A>(), type_name::<B>())
+    }
+
+    fn parse(&self, input: &Input) -> Result<Box<dyn Any>> {
+        Ok(Box::new((self.parse)(input)?))
+    }
+
+    fn part1(&self, parsed: &dyn Any) -> Result<Answer> {
+        Ok((self.part1)(self.downcast(parsed)?)?.into())
+    }
+
+    fn part2(&self, parsed: &dyn Any) -> Result<Answer> {
+        Ok((self.part2)(self.downcast(parsed)?)?.into())
+    }
+}
+
+#[test]
+fn test_day_names() {
+    fn parse(input: &Input) -> Result<usize> {
+        Ok(input.all().len())
+    }
+
+    let day = Day::new("y2020", "d05", parse, |n| Ok(*n), |_| Ok("text"));
+    assert_eq!((2020, 5, None), (day.year(), day.day(), day.variant()));
+    assert_eq!("y2020::d05", day.name());
+    assert_eq!(("usize", "&str"), day.answer_types());
+    assert_eq!(
+        (Answer::Integer(3), Answer::Text("text".into())),
+        day.run(&Input::from("abc")).unwrap()
+    );
+
+    let day = Day::new("y2021", "d07_opt", parse, |_| Ok(-2), |_| Ok(u64::MAX));
+    assert_eq!(
+        (2021, 7, Some("opt")),
+        (day.year(), day.day(), day.variant())
+    );
+    assert_eq!("y2021::d07_opt", day.name());
+    assert_eq!(
+        (Answer::Integer(-2), Answer::BigInteger(u64::MAX as i128)),
+        day.run(&Input::default()).unwrap()
+    );
+    assert!(day.part1(&"wrong type").is_err());
+}
@@ -1,84 +1,293 @@
+use crate::utils::ParseError;
 use anyhow::{bail, Result};
 use log::debug;
 use std::borrow::Borrow;
-use std::fmt::Display;
 use std::fs::File;
 use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::str::{FromStr, Lines};
 
+/// Inputs are grouped in profiles, usually one per account, stored in `input/<profile>/`.
+/// Every day has an input in this profile, other profiles can be partial.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// Directory of `input/` holding the puzzle examples, which is not a profile
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// Lists the input profiles, sorted by name
+pub fn profiles() -> Result<Vec<String>> {
+    let mut profiles = Vec::new();
+    for entry in std::fs::read_dir("input")? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() && entry.file_name() != EXAMPLES_DIR {
+            profiles.push(entry.file_name().to_string_lossy().into_owned());
+        }
+    }
+    profiles.sort();
+    Ok(profiles)
+}
+
 #[derive(Default)]
-pub struct Input(String);
+pub struct Input {
+    content: String,
+    /// Day the input belongs to, reported in parsing errors
+    day: Option<String>,
+}
 
 impl Input {
+    /// Reads the input of a day from the default profile
     pub fn open(year: &str, day: &str) -> Result<Input> {
+        Self::open_profile(DEFAULT_PROFILE, year, day)
+    }
+
+    /// Reads the input of a day from a profile, shared by all variants of the day
+    pub fn open_profile(profile: &str, year: &str, day: &str) -> Result<Input> {
+        Self::read_file(Self::path(profile, year, day))
+    }
+
+    /// Location of the input of a day in a profile: `input/<profile>/yYYYY/dDD`
+    pub fn path(profile: &str, year: &str, day: &str) -> PathBuf {
         let day = match day.find('_') {
             None => day,
             Some(pos) => day.split_at(pos).0,
         };
-        let path = format!["input/{}/{}", year, day];
-        debug!["Reading input from {}", path];
+        PathBuf::from(format!["input/{}/{}/{}", profile, year, day])
+    }
 
-        let mut input: Input = Input::default();
-        File::open(path)?.read_to_string(&mut input.0)?;
+    /// Reads the input from an arbitrary file
+    pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Input> {
+        debug!["Reading input from {}", path.as_ref().display()];
+        Self::read_from(File::open(path)?)
+    }
 
+    /// Reads the input from any reader, for example stdin
+    pub fn read_from<R: Read>(mut reader: R) -> Result<Input> {
+        let mut input: Input = Input::default();
+        reader.read_to_string(&mut input.content)?;
         Ok(input)
     }
 
     pub fn from(content: &str) -> Input {
-        Self(content.to_owned())
+        Self {
+            content: content.to_owned(),
+            day: None,
+        }
+    }
+
+    /// Sets the day this input belongs to, for parsing errors
+    pub fn with_day(mut self, day: &str) -> Input {
+        self.day = Some(day.to_owned());
+        self
     }
 
     pub fn all(&self) -> &str {
-        self.0.borrow()
+        self.content.borrow()
     }
 
-    pub fn lines(&self) -> Lines {
-        self.0.lines()
+    pub fn lines(&self) -> Lines<'_> {
+        self.content.lines()
     }
 
+    /// Error for a line, `line` counting from 0
+    pub fn error(&self, line: usize, error: anyhow::Error) -> ParseError {
+        let text = self.lines().nth(line).unwrap_or_default();
+        ParseError::new(self.day.as_deref(), line, text, error)
+    }
+
+    /// Parses the non-empty lines with a function
     pub fn lines_with<T>(&self, f: fn(&str) -> Result<T>) -> Result<Vec<T>> {
-        Ok(self
-            .lines()
-            .filter(|s| !s.is_empty())
-            .map(|l| f(l).unwrap())
-            .collect())
+        let mut parsed: Vec<T> = Vec::new();
+        for (line, value) in self.lines().enumerate().filter(|(_, s)| !s.is_empty()) {
+            match f(value) {
+                Ok(result) => parsed.push(result),
+                Err(err) => return Err(self.error(line, err).into()),
+            }
+        }
+        Ok(parsed)
     }
 
+    /// Parses the non-empty lines with a function and sums the values
     pub fn lines_summed<T: std::iter::Sum>(&self, f: fn(&str) -> Result<T>) -> Result<T> {
-        Ok(self
-            .lines()
-            .filter(|s| !s.is_empty())
-            .map(|l| f(l).unwrap())
-            .sum())
+        Ok(self.lines_with(f)?.into_iter().sum())
     }
 
     pub fn lines_into<T: FromStr>(&self) -> Result<Vec<T>>
     where
-        <T as FromStr>::Err: Display,
+        <T as FromStr>::Err: Into<anyhow::Error>,
     {
         let mut parsed: Vec<T> = Vec::new();
         for (line, value) in self.lines().enumerate() {
             match T::from_str(value) {
                 Ok(result) => parsed.push(result),
-                Err(err) => bail!("error parsing line {}: {}", line, err),
+                Err(err) => return Err(self.error(line, err.into()).into()),
             }
         }
         Ok(parsed)
     }
+
+    /// Byte ranges of the records separated by blank lines, without surrounding whitespace
+    fn block_spans(&self) -> Vec<(usize, usize)> {
+        let mut spans = Vec::new();
+        let (mut offset, mut current) = (0, None);
+        for line in self.content.split_inclusive('\n') {
+            if line.trim().is_empty() {
+                spans.extend(current.take());
+            } else {
+                let end = offset + line.trim_end().len();
+                current = Some((current.map_or(offset, |(start, _)| start), end));
+            }
+            offset += line.len();
+        }
+        spans.extend(current);
+        spans
+    }
+
+    /// Iterates on the records separated by blank lines, ignoring extra blank lines
+    pub fn blocks(&self) -> impl Iterator<Item = &str> {
+        let spans = self.block_spans();
+        spans
+            .into_iter()
+            .map(|(start, end)| &self.content[start..end])
+    }
+
+    /// Parses each record separated by blank lines, errors pointing at the first line of the
+    /// record
+    pub fn blocks_into<T: FromStr>(&self) -> Result<Vec<T>>
+    where
+        <T as FromStr>::Err: Into<anyhow::Error>,
+    {
+        let mut parsed: Vec<T> = Vec::new();
+        for (start, end) in self.block_spans() {
+            match T::from_str(&self.content[start..end]) {
+                Ok(result) => parsed.push(result),
+                Err(err) => {
+                    let line = self.content[..start].matches('\n').count();
+                    return Err(self.error(line, err.into()).into());
+                }
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Splits the input in two at the first blank line, the second section running until the
+    /// end of the input
+    pub fn sections(&self) -> Result<(&str, &str)> {
+        let spans = self.block_spans();
+        match (spans.first(), spans.get(1), spans.last()) {
+            (Some(first), Some(second), Some(last)) => Ok((
+                &self.content[first.0..first.1],
+                &self.content[second.0..last.1],
+            )),
+            _ => bail!("expected two sections separated by a blank line"),
+        }
+    }
 }
 
+/// Registers the solutions of a year and generates one test per day, checking its answers
+/// against `input/<profile>/yYYYY/answers.toml` for every profile with an input for the day.
+/// Day modules are named `dNN`, alternative implementations are suffixed: `dNN_variant` and
+/// listed after their base day: `d05 | d05_opt`. The test also checks that variants return
+/// the same answers as the base day. Each module provides `parse`, `part1` and `part2`
+/// functions.
+///
+/// The build script also generates one `dNN_example_<name>` test per file in `input/examples/`.
 #[macro_export]
-macro_rules! generate_tests {
-    ($year:ident, $($day:ident: $expected:expr,)+) => {
+macro_rules! register_solutions {
+    (@day $year:ident, $day:ident) => {
+        // Closures allow part functions to take deref targets, like &[T] for a Vec<T>
+        $crate::utils::Day::new(
+            stringify!($year),
+            stringify!($day),
+            $day::parse,
+            |parsed| $day::part1(parsed),
+            |parsed| $day::part2(parsed),
+        )
+    };
+    ($year:ident, $($day:ident $(| $variant:ident)*,)+) => {
+    /// Lists the solutions implemented for this year, variants following their base day
+    pub fn solutions() -> Vec<Box<dyn $crate::utils::Solution>> {
+        vec![
+        $(
+            Box::new($crate::register_solutions!(@day $year, $day)),
+            $( Box::new($crate::register_solutions!(@day $year, $variant)), )*
+        )*
+        ]
+    }
+
     $(
         #[test]
         fn $day() -> anyhow::Result<()> {
             let _ = pretty_env_logger::try_init();
-            let input = $crate::utils::Input::open(stringify!($year), stringify!($day)).unwrap();
-            let output =  $crate::$year::$day::run(&input)?;
-            assert_eq!($expected, output);
-            Ok(())
+            let solutions: Vec<Box<dyn $crate::utils::Solution>> = vec![
+                Box::new($crate::register_solutions!(@day $year, $day)),
+                $( Box::new($crate::register_solutions!(@day $year, $variant)), )*
+            ];
+            $crate::utils::check_answers(&solutions)
         }
     )*
+
+    include!(concat!(env!("OUT_DIR"), "/examples_", stringify!($year), ".rs"));
+    }
+}
+
+#[test]
+fn test_blocks() -> Result<()> {
+    let input = Input::from("\n1\n2 \r\n\r\n  \n3\r\n\n\n4\n5\n\n");
+    let blocks: Vec<&str> = input.blocks().collect();
+    assert_eq!(vec!["1\n2", "3", "4\n5"], blocks);
+    assert_eq!(
+        vec![3, 3, 9],
+        input
+            .blocks_into::<Sum>()?
+            .iter()
+            .map(|s| s.0)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(("1\n2", "3\r\n\n\n4\n5"), input.sections()?);
+    assert!(Input::from("1\n2\n").sections().is_err());
+    assert!(Input::from("1\n\nx").blocks_into::<Sum>().is_err());
+    Ok(())
+}
+
+#[test]
+fn test_line_errors() {
+    let input = Input::from("1\n\n3\nx4\n").with_day("y2020::d01");
+    let error = input.lines_summed(|l| Ok(l.parse::<u32>()?)).unwrap_err();
+    let error = error.downcast::<ParseError>().unwrap();
+    assert_eq!(4, error.line);
+    assert_eq!(Some("y2020::d01"), error.day.as_deref());
+    assert_eq!(1..3, error.columns);
+    assert!(input.lines_into::<u32>().is_err());
+    assert!(Input::from("1\n\n2\n3\n\nx")
+        .blocks_into::<Sum>()
+        .unwrap_err()
+        .to_string()
+        .starts_with("line 6, column 1: "));
+}
+
+#[cfg(test)]
+#[derive(Debug)]
+struct Sum(u32);
+
+#[cfg(test)]
+impl FromStr for Sum {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.lines()
+            .map(u32::from_str)
+            .sum::<Result<u32, _>>()
+            .map(Sum)
     }
 }
+
+#[test]
+fn test_input_profiles() -> Result<()> {
+    assert_eq!(
+        PathBuf::from("input/alice/y2020/d05"),
+        Input::path("alice", "y2020", "d05_opt")
+    );
+    assert!(profiles()?.iter().any(|p| p == DEFAULT_PROFILE));
+    assert!(!profiles()?.iter().any(|p| p == EXAMPLES_DIR));
+    Ok(())
+}
@@ -0,0 +1,150 @@
+use crate::utils::{Input, Solution};
+use anyhow::Result;
+use log::info;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+/// Days faster than this are never flagged as regressions, their timings are mostly noise
+const NOISE_FLOOR: Duration = Duration::from_millis(1);
+
+/// Wall-clock time spent in each step of a solution, in microseconds
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub struct DayTiming {
+    pub parse: u64,
+    pub part1: u64,
+    pub part2: u64,
+}
+
+impl DayTiming {
+    /// Runs a solution once on the given input and times each step
+    pub fn measure(solution: &dyn Solution, input: &Input) -> Result<Self> {
+        let start = Instant::now();
+        let parsed = solution.parse(input)?;
+        let parse = start.elapsed();
+        solution.part1(parsed.as_ref())?;
+        let part1 = start.elapsed() - parse;
+        solution.part2(parsed.as_ref())?;
+        let part2 = start.elapsed() - parse - part1;
+        Ok(Self {
+            parse: parse.as_micros() as u64,
+            part1: part1.as_micros() as u64,
+            part2: part2.as_micros() as u64,
+        })
+    }
+
+    pub fn total(&self) -> Duration {
+        Duration::from_micros(self.parse + self.part1 + self.part2)
+    }
+}
+
+/// Timings of several solutions, indexed by solution name, stored as JSON
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Timings(BTreeMap<String, DayTiming>);
+
+impl Timings {
+    /// Reads timings saved by `save`, a missing file means there is no baseline yet
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        info!["Writing timings to {}", path.as_ref().display()];
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Saves these timings into the baseline at `path`, keeping the days that were not
+    /// measured this time
+    pub fn update<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let mut baseline = Self::open(&path)?.unwrap_or_default();
+        baseline.0.extend(self.0.clone());
+        baseline.save(path)
+    }
+
+    pub fn insert(&mut self, name: String, timing: DayTiming) {
+        self.0.insert(name, timing);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&DayTiming> {
+        self.0.get(name)
+    }
+
+    /// Lists the timings from the slowest to the fastest day
+    pub fn slowest_first(&self) -> Vec<(&str, &DayTiming)> {
+        let mut timings: Vec<(&str, &DayTiming)> =
+            self.0.iter().map(|(n, t)| (n.as_str(), t)).collect();
+        timings.sort_by_key(|(_, t)| std::cmp::Reverse(t.total()));
+        timings
+    }
+
+    /// Slowdown ratio of a day compared to the baseline, when it exceeds `max_ratio`.
+    /// Days missing from the baseline, or fast enough to be noise, are never flagged.
+    pub fn regression(&self, name: &str, baseline: &Timings, max_ratio: f64) -> Option<f64> {
+        let current = self.get(name)?.total();
+        let previous = baseline.get(name)?.total();
+        if current < NOISE_FLOOR {
+            return None;
+        }
+        let ratio = current.as_secs_f64() / previous.as_secs_f64().max(1e-6);
+        (ratio > max_ratio).then_some(ratio)
+    }
+}
+
+#[test]
+fn test_regression() -> Result<()> {
+    let timing = |parse, part1, part2| DayTiming {
+        parse,
+        part1,
+        part2,
+    };
+    let baseline: Timings = serde_json::from_str(
+        r#"{"y2020::d01": {"parse": 100, "part1": 1000, "part2": 900},
+            "y2020::d02": {"parse": 10, "part1": 20, "part2": 30}}"#,
+    )?;
+    let mut current = Timings::default();
+    current.insert("y2020::d01".into(), timing(100, 2000, 1900));
+    current.insert("y2020::d02".into(), timing(100, 200, 300));
+    current.insert("y2020::d03".into(), timing(0, 5000, 5000));
+
+    assert_eq!(Some(2.0), current.regression("y2020::d01", &baseline, 1.5));
+    assert_eq!(None, current.regression("y2020::d01", &baseline, 2.5));
+    // Under the noise floor
+    assert_eq!(None, current.regression("y2020::d02", &baseline, 1.5));
+    // Not in the baseline
+    assert_eq!(None, current.regression("y2020::d03", &baseline, 1.5));
+
+    let names: Vec<&str> = current.slowest_first().iter().map(|(n, _)| *n).collect();
+    assert_eq!(vec!["y2020::d03", "y2020::d01", "y2020::d02"], names);
+    Ok(())
+}
+
+#[test]
+fn test_update() -> Result<()> {
+    let path = std::env::temp_dir().join(format!("aoc-timings-{}.json", std::process::id()));
+    let timing = |total| DayTiming {
+        parse: 0,
+        part1: total,
+        part2: 0,
+    };
+    let mut all = Timings::default();
+    all.insert("y2020::d01".into(), timing(10));
+    all.insert("y2021::d01".into(), timing(20));
+    all.update(&path)?;
+
+    // Only 2021 measured again
+    let mut filtered = Timings::default();
+    filtered.insert("y2021::d01".into(), timing(30));
+    filtered.update(&path)?;
+    let saved = Timings::open(&path)?.unwrap_or_default();
+    std::fs::remove_file(&path)?;
+    assert_eq!(Some(10), saved.get("y2020::d01").map(|t| t.part1));
+    assert_eq!(Some(30), saved.get("y2021::d01").map(|t| t.part1));
+    Ok(())
+}
@@ -0,0 +1,397 @@
+use crate::utils::Input;
+use crate::y2019::intcode::{Instruction, Mode, Opcode, Parameter};
+use anyhow::{anyhow, bail, Result};
+use std::collections::HashMap;
+
+/// Maximum nesting of macro invocations, stopping recursive macros
+const MAX_MACRO_DEPTH: usize = 16;
+
+/// Assembles a program written in the syntax of the disassembler, one statement per line:
+/// ```text
+/// ; Outputs the sum of two inputs
+/// macro read dst
+///     in -> dst
+/// endm
+///     read [a]
+///     read [b]
+///     add [a] [b] -> [a]
+///     out [a]
+///     hlt
+/// a:  data 0
+/// b:  data 0
+/// ```
+/// - `;` starts a comment, `name:` defines a label at the address of what follows
+/// - operands are `#value` in immediate mode, `[value]` in position mode and `[rb+value]` in
+///   relative mode, the written operand following `->`
+/// - values are numbers, labels, or labels with an offset like `table+2`
+/// - `data` stores values as they are
+/// - `macro name params...` up to `endm` defines a macro, whose parameters are replaced by the
+///   arguments of each invocation. `\@` is replaced by a number unique to the invocation, to
+///   name labels local to a macro.
+pub fn assemble(source: &Input) -> Result<Vec<isize>> {
+    let mut assembler = Assembler::default();
+    for (line, text) in source.lines().enumerate() {
+        assembler
+            .statement(line, text, 0)
+            .map_err(|err| source.error(line, err))?;
+    }
+    if let Some((name, _, line)) = assembler.defining {
+        bail!(source.error(line, anyhow!("macro {} is missing its endm", name)))
+    }
+
+    let mut program = Vec::with_capacity(assembler.size);
+    for (line, statement) in &assembler.statements {
+        let values = assembler
+            .encode(statement)
+            .map_err(|err| source.error(*line, err))?;
+        program.extend(values);
+    }
+    Ok(program)
+}
+
+/// Number, or label address, plus an offset
+#[derive(Debug, Clone)]
+struct Value {
+    label: Option<String>,
+    offset: isize,
+}
+
+#[derive(Debug, Clone)]
+enum Statement {
+    Instruction(Opcode, Vec<(Mode, Value)>),
+    Data(Vec<Value>),
+}
+
+#[derive(Debug, Clone)]
+struct Macro {
+    params: Vec<String>,
+    body: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+struct Assembler {
+    macros: HashMap<String, Macro>,
+    /// Macro whose body is being read, with the line of its definition
+    defining: Option<(String, Macro, usize)>,
+    expansions: usize,
+    labels: HashMap<String, usize>,
+    /// Statements with their source line, the line of the invocation for macros
+    statements: Vec<(usize, Statement)>,
+    size: usize,
+}
+
+impl Assembler {
+    /// Reads a line, defining its labels and macros and recording its statements
+    fn statement(&mut self, line: usize, text: &str, depth: usize) -> Result<()> {
+        let text = text.split(';').next().unwrap_or_default().trim();
+        if let Some((name, mut definition, start)) = self.defining.take() {
+            match text {
+                "endm" => {
+                    self.macros.insert(name, definition);
+                }
+                _ => {
+                    definition.body.push(text.to_owned());
+                    self.defining = Some((name, definition, start));
+                }
+            }
+            return Ok(());
+        }
+
+        let mut tokens: Vec<&str> = text.split_whitespace().collect();
+        while let Some(label) = tokens.first().and_then(|t| t.strip_suffix(':')) {
+            self.define(label)?;
+            tokens.remove(0);
+        }
+        let Some((&name, args)) = tokens.split_first() else {
+            return Ok(());
+        };
+        match name {
+            "macro" => {
+                let Some((name, params)) = args.split_first() else {
+                    bail!("missing macro name")
+                };
+                check_name(name)?;
+                params.iter().try_for_each(|p| check_name(p))?;
+                if self.macros.contains_key(*name) || Opcode::from_mnemonic(name).is_some() {
+                    bail!("{} is already defined", name)
+                }
+                let definition = Macro {
+                    params: params.iter().map(|p| p.to_string()).collect(),
+                    body: vec![],
+                };
+                self.defining = Some((name.to_string(), definition, line));
+            }
+            "endm" => bail!("endm outside of a macro"),
+            "data" => {
+                let values = args.iter().map(|a| parse_value(a)).collect::<Result<_>>()?;
+                self.push(line, Statement::Data(values));
+            }
+            _ => match Opcode::from_mnemonic(name) {
+                Some(opcode) => {
+                    let operands = parse_operands(opcode, args)?;
+                    self.push(line, Statement::Instruction(opcode, operands));
+                }
+                None => self.expand(line, name, args, depth)?,
+            },
+        }
+        Ok(())
+    }
+
+    fn define(&mut self, label: &str) -> Result<()> {
+        check_name(label)?;
+        if self.labels.insert(label.to_owned(), self.size).is_some() {
+            bail!("label {} is already defined", label)
+        }
+        Ok(())
+    }
+
+    fn push(&mut self, line: usize, statement: Statement) {
+        self.size += match &statement {
+            Statement::Instruction(_, operands) => 1 + operands.len(),
+            Statement::Data(values) => values.len(),
+        };
+        self.statements.push((line, statement));
+    }
+
+    /// Reads the body of a macro with the arguments of an invocation
+    fn expand(&mut self, line: usize, name: &str, args: &[&str], depth: usize) -> Result<()> {
+        let Some(definition) = self.macros.get(name).cloned() else {
+            bail!("unknown instruction or macro {}", name)
+        };
+        if depth >= MAX_MACRO_DEPTH {
+            bail!("macros nested more than {} times", MAX_MACRO_DEPTH)
+        }
+        // Arrows may be written to show the outputs, like for instructions
+        let args: Vec<&str> = args.iter().copied().filter(|a| *a != "->").collect();
+        if args.len() != definition.params.len() {
+            bail!(
+                "macro {} takes {} arguments, got {}",
+                name,
+                definition.params.len(),
+                args.len()
+            )
+        }
+        self.expansions += 1;
+        let unique = self.expansions.to_string();
+        for text in &definition.body {
+            let text = substitute(text, &definition.params, &args).replace("\\@", &unique);
+            self.statement(line, &text, depth + 1)
+                .map_err(|err| err.context(format!("in macro {}", name)))?;
+        }
+        Ok(())
+    }
+
+    fn resolve(&self, value: &Value) -> Result<isize> {
+        let base = match &value.label {
+            None => 0,
+            Some(label) => match self.labels.get(label) {
+                Some(address) => *address as isize,
+                None => bail!("undefined label {}", label),
+            },
+        };
+        Ok(base + value.offset)
+    }
+
+    fn encode(&self, statement: &Statement) -> Result<Vec<isize>> {
+        Ok(match statement {
+            Statement::Instruction(opcode, operands) => {
+                let params = operands
+                    .iter()
+                    .map(|(mode, value)| {
+                        let value = self.resolve(value)?;
+                        Ok(Parameter { mode: *mode, value })
+                    })
+                    .collect::<Result<_>>()?;
+                let opcode = *opcode;
+                Instruction { opcode, params }.encode()
+            }
+            Statement::Data(values) => values
+                .iter()
+                .map(|v| self.resolve(v))
+                .collect::<Result<_>>()?,
+        })
+    }
+}
+
+/// Checks a label, macro or parameter name, made of letters, digits, `_` and `.`
+fn check_name(name: &str) -> Result<()> {
+    let valid = name.starts_with(|c: char| c.is_alphabetic() || c == '_' || c == '.')
+        && name.chars().all(is_name_char)
+        && name != "rb";
+    match valid {
+        true => Ok(()),
+        false => bail!("invalid name {:?}", name),
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_' || c == '.'
+}
+
+/// Replaces the names of the parameters by the arguments
+fn substitute(text: &str, params: &[String], args: &[&str]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find(is_name_char) {
+        result.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let end = rest.find(|c| !is_name_char(c)).unwrap_or(rest.len());
+        let word = &rest[..end];
+        match params.iter().position(|p| p == word) {
+            Some(index) => result.push_str(args[index]),
+            None => result.push_str(word),
+        }
+        rest = &rest[end..];
+    }
+    result.push_str(rest);
+    result
+}
+
+/// Parses a number, a label, or a label with an offset
+fn parse_value(text: &str) -> Result<Value> {
+    if let Ok(offset) = text.parse() {
+        return Ok(Value {
+            label: None,
+            offset,
+        });
+    }
+    let (label, offset) = match text.find(['+', '-']) {
+        Some(sign) => match text[sign..].parse() {
+            Ok(offset) => (&text[..sign], offset),
+            Err(_) => bail!("invalid offset in {}", text),
+        },
+        None => (text, 0),
+    };
+    check_name(label).map_err(|_| anyhow!("invalid value {:?}", text))?;
+    Ok(Value {
+        label: Some(label.to_owned()),
+        offset,
+    })
+}
+
+/// Parses an operand: `#value`, `[value]` or `[rb+value]`
+fn parse_operand(text: &str) -> Result<(Mode, Value)> {
+    if let Some(value) = text.strip_prefix('#') {
+        return Ok((Mode::Immediate, parse_value(value)?));
+    }
+    let Some(inner) = text.strip_prefix('[').and_then(|t| t.strip_suffix(']')) else {
+        bail!(
+            "invalid operand {:?}, expected #value, [address] or [rb+offset]",
+            text
+        )
+    };
+    match inner.strip_prefix("rb") {
+        Some("") => Ok((
+            Mode::Relative,
+            Value {
+                label: None,
+                offset: 0,
+            },
+        )),
+        Some(offset) if offset.starts_with(['+', '-']) => {
+            let offset = offset.strip_prefix('+').unwrap_or(offset);
+            Ok((Mode::Relative, parse_value(offset)?))
+        }
+        _ => Ok((Mode::Position, parse_value(inner)?)),
+    }
+}
+
+/// Parses the operands of an instruction, the written one following `->`
+fn parse_operands(opcode: Opcode, args: &[&str]) -> Result<Vec<(Mode, Value)>> {
+    let (inputs, output) = match args.iter().position(|a| *a == "->") {
+        Some(arrow) => (&args[..arrow], &args[arrow + 1..]),
+        None => (args, &[][..]),
+    };
+    if inputs.len() != opcode.reads() || output.len() != opcode.writes() as usize {
+        bail!(
+            "{} takes {} inputs and {} outputs, got {} and {}",
+            opcode.mnemonic(),
+            opcode.reads(),
+            opcode.writes() as usize,
+            inputs.len(),
+            output.len()
+        )
+    }
+    let operands: Vec<(Mode, Value)> = args
+        .iter()
+        .filter(|a| **a != "->")
+        .map(|a| parse_operand(a))
+        .collect::<Result<_>>()?;
+    if output.len() == 1 && operands[operands.len() - 1].0 == Mode::Immediate {
+        bail!("{} cannot write to an immediate operand", opcode.mnemonic())
+    }
+    Ok(operands)
+}
+
+#[test]
+fn test_assemble() -> Result<()> {
+    use crate::y2019::computer::Computer;
+    use crate::y2019::intcode::format_program;
+
+    let source = Input::from(
+        "
+        ; Outputs the largest of two inputs, then their sum
+        macro max a b dst
+            lt a b -> [.tmp]
+            jnz [.tmp] #.b\\@
+            add a #0 -> dst
+            jz #0 #.end\\@
+        .b\\@: add b #0 -> dst
+        .end\\@:
+        endm
+
+        start:  in -> [x]
+                in -> [y]
+                max [x] [y] -> [out]
+                out [out]
+                add [x] [y] -> [out]
+                out [out]
+                hlt
+        x:      data 0
+        y:      data 0
+        out:    data 0
+        .tmp:   data start-1 x+1
+        ",
+    );
+    let program = assemble(&source)?;
+    assert_eq!(&[3, 31, 3, 32, 7, 31, 32, 34, 1005, 34, 18], &program[..11]);
+    assert_eq!(&[0, 0, 0, -1, 32], &program[31..]);
+
+    let mut computer = Computer::new(&Input::from(&format_program(&program)))?;
+    computer.push_input(7);
+    computer.push_input(12);
+    computer.execute()?;
+    assert_eq!(vec![12, 19], computer.get_all_output());
+
+    let relative = assemble(&Input::from("arb #x\nout [rb-34]\nx: in -> [rb+x]"))?;
+    assert_eq!(vec![109, 4, 204, -34, 203, 4], relative);
+
+    let error = |source: &str| assemble(&Input::from(source)).unwrap_err().to_string();
+    assert!(error("hlt\nadd #1 [2]").starts_with("line 2, columns 1-10: add takes 2 inputs"));
+    assert!(error("add #1 #2 -> #3").contains("immediate operand"));
+    assert!(error("jz #0 #end").contains("undefined label end"));
+    assert!(error("a: hlt\na: hlt").contains("label a is already defined"));
+    assert!(error("mov [1] [2]").contains("unknown instruction or macro mov"));
+    assert!(error("macro m\nm\nendm\nm").contains("in macro m: in macro m"));
+    assert!(error("macro m x\nout x").contains("missing its endm"));
+    Ok(())
+}
+
+#[test]
+fn test_round_trip() -> Result<()> {
+    use crate::y2019::disassembler::disassemble;
+    use crate::y2019::intcode::parse_program;
+
+    let example = "3,9,8,9,10,9,4,9,99,-1,8,1105,1,9,109,19,204,-34,99";
+    let mut programs = vec![parse_program(&Input::from(example))?];
+    for day in ["d02", "d05"] {
+        if let Ok(input) = Input::open("y2019", day) {
+            programs.push(parse_program(&input)?);
+        }
+    }
+    for program in programs {
+        let listing = disassemble(&program).to_string();
+        assert_eq!(program, assemble(&Input::from(&listing))?);
+    }
+    Ok(())
+}
@@ -1,53 +1,117 @@
 use crate::utils::Input;
 use crate::y2019::computer::AccessMode::{Read, Write};
+use crate::y2019::decoded::{check_address, DecodeCache, Decoded};
+use crate::y2019::intcode::{parse_program, Opcode};
 use anyhow::{bail, Result};
+use log::info;
+use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
-use std::str::FromStr;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
 enum AccessMode {
     Read,
     Write,
 }
 
+/// Effect of executing one instruction
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Step {
+    /// The instruction ran, with the address it wrote to and the value it replaced
+    Ran { write: Option<(usize, isize)> },
+    /// The cursor is on intcode 99, where it stays
+    Halted,
+}
+
+/// Reason `run` stopped
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Status {
+    Halted,
+    /// The cursor is on an input intcode and no input is queued
+    NeedsInput,
+}
+
+/// Complete state of a computer at a point of its execution, to restore it later or resume
+/// a long run from a file
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// Hash of the program the snapshot was taken from
+    pub program: u64,
+    pub memory: Vec<isize>,
+    pub cursor: usize,
+    pub input: VecDeque<isize>,
+    pub output: Vec<isize>,
+}
+
+impl Snapshot {
+    /// Reads a snapshot written by `save`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        info!["Writing Intcode snapshot to {}", path.as_ref().display()];
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
 /// A computer is instantiated with a read-only program.
 /// The program is loaded into memory on reset and can be executed in place.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Computer {
-    program: Vec<isize>,
+    program: Arc<[isize]>, // Shared by forks
+    program_hash: u64,
     memory: Vec<isize>,
     input: VecDeque<isize>,
     output: Vec<isize>,
 
     cursor: usize, // Cursor
     modes: usize,  // Access modes from the last opcode
+    last_write: Option<(usize, isize)>,
+    /// Instructions decoded once, to run faster programs executed many times
+    decoder: Option<DecodeCache>,
 }
 
 impl Computer {
     /// Reads the program and instantiates the work memory to a copy of the program
     pub fn new(input: &Input) -> Result<Self> {
-        let mut program: Vec<isize> = Vec::new();
-        for value in input.all().trim().split(',') {
-            program.push(isize::from_str(value)?);
-        }
+        let program = parse_program(input)?;
         Ok(Self {
             memory: program.clone(),
-            program,
+            program_hash: hash_program(&program),
+            program: program.into(),
             input: VecDeque::new(),
             output: vec![],
             cursor: 0,
             modes: 0,
+            last_write: None,
+            decoder: None,
         })
     }
 
+    /// Caches the decoded instructions by address instead of decoding them at every
+    /// execution, faster on programs run many times. Writes to the code decode it again.
+    pub fn with_decoding(mut self) -> Self {
+        self.decoder = Some(DecodeCache::new(&self.program));
+        self
+    }
+
     /// Resets the work memory from the program
     pub fn reset(&mut self) {
-        self.memory = self.program.clone();
+        self.memory.clear();
+        self.memory.extend_from_slice(&self.program);
+        if let Some(decoder) = &mut self.decoder {
+            decoder.reset();
+        }
+        self.cursor = 0;
         self.input.clear();
         self.output.clear();
     }
 
     /// Alters the work memory by storing an arbitrary value
     pub fn alter_memory(&mut self, address: usize, value: isize) {
-        self.memory[address] = value
+        self.write(address, value)
     }
 
     /// Get the value stored at a given address
@@ -68,6 +132,61 @@ impl Computer {
         }
     }
 
+    /// Copies the state of the computer, to restore it later
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            program: self.program_hash,
+            memory: self.memory.clone(),
+            cursor: self.cursor,
+            input: self.input.clone(),
+            output: self.output.clone(),
+        }
+    }
+
+    /// Puts the computer back in the state of a snapshot of the same program
+    pub fn restore(&mut self, snapshot: &Snapshot) -> Result<()> {
+        if snapshot.program != self.program_hash {
+            bail!("Snapshot of another program")
+        }
+        if snapshot.memory.len() != self.program.len() {
+            bail!(
+                "Snapshot of {} values for a program of {}",
+                snapshot.memory.len(),
+                self.program.len()
+            )
+        }
+        self.check_cursor_at(snapshot.cursor)?;
+        self.memory.copy_from_slice(&snapshot.memory);
+        if let Some(decoder) = &mut self.decoder {
+            decoder.forget_all();
+        }
+        self.cursor = snapshot.cursor;
+        self.input.clone_from(&snapshot.input);
+        self.output.clone_from(&snapshot.output);
+        Ok(())
+    }
+
+    /// Copies the computer to explore an alternative from its current state, the program
+    /// being shared
+    pub fn fork(&self) -> Computer {
+        self.clone()
+    }
+
+    /// Address of the next instruction to execute
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// Work memory, as modified by the execution
+    pub fn memory(&self) -> &[isize] {
+        &self.memory
+    }
+
+    /// Values waiting to be read by the input intcode
+    pub fn input(&self) -> &VecDeque<isize> {
+        &self.input
+    }
+
     /// Get a copy of all outputs in order
     pub fn get_all_output(&self) -> Vec<isize> {
         self.output.clone()
@@ -75,12 +194,12 @@ impl Computer {
 
     /// Checks the current cursor is within bounds
     fn check_cursor(&self) -> Result<()> {
-        if self.cursor >= self.memory.len() {
-            bail!(
-                "Cursor out of bounds: {}>={}",
-                self.cursor,
-                self.memory.len()
-            )
+        self.check_cursor_at(self.cursor)
+    }
+
+    fn check_cursor_at(&self, cursor: usize) -> Result<()> {
+        if cursor >= self.memory.len() {
+            bail!("Cursor out of bounds: {}>={}", cursor, self.memory.len())
         }
         Ok(())
     }
@@ -141,10 +260,19 @@ impl Computer {
 
     fn write_value(&mut self, value: isize) -> Result<()> {
         let addr = self.next_address(Write)?;
-        self.memory[addr] = value;
+        self.last_write = Some((addr, self.memory[addr]));
+        self.write(addr, value);
         Ok(())
     }
 
+    /// Stores a value, forgetting the decoded instructions it overlaps
+    fn write(&mut self, address: usize, value: isize) {
+        self.memory[address] = value;
+        if let Some(decoder) = &mut self.decoder {
+            decoder.forget(address);
+        }
+    }
+
     fn write_bool(&mut self, value: bool) -> Result<()> {
         if value {
             self.write_value(1)
@@ -156,70 +284,207 @@ impl Computer {
     /// Executes the program from the beginning until intcode 99
     pub fn execute(&mut self) -> Result<()> {
         self.cursor = 0;
+        while self.step()? != Step::Halted {}
+        Ok(())
+    }
+
+    /// Executes from the cursor until intcode 99, or until an input is needed. Inputs can
+    /// then be pushed before running again.
+    pub fn run(&mut self) -> Result<Status> {
         loop {
-            match self.read_code()? {
-                1 => {
-                    let v = self.read_value()? + self.read_value()?;
-                    self.write_value(v)?;
-                }
-                2 => {
-                    let v = self.read_value()? * self.read_value()?;
-                    self.write_value(v)?;
-                }
-                3 => match self.input.pop_front() {
-                    None => bail!("No input to read"),
-                    Some(i) => self.write_value(i)?,
-                },
-                4 => {
-                    let v = self.read_value()?;
-                    self.output.push(v)
-                }
-                5 => {
-                    let jump = self.read_value()? != 0;
-                    let p = self.read_value()?;
-                    if jump {
-                        self.cursor = self.convert_to_cursor(p)?;
-                    }
-                }
-                6 => {
-                    let jump = self.read_value()? == 0;
-                    let p = self.read_value()?;
-                    if jump {
-                        self.cursor = self.convert_to_cursor(p)?;
+            let code = self.memory.get(self.cursor).map(|value| value % 100);
+            if code == Some(3) && self.input.is_empty() {
+                return Ok(Status::NeedsInput);
+            }
+            if self.step()? == Step::Halted {
+                return Ok(Status::Halted);
+            }
+        }
+    }
+
+    /// Executes the instruction at the cursor. On errors, like a missing input, the cursor is
+    /// left on the instruction so that it can be retried.
+    #[inline]
+    pub fn step(&mut self) -> Result<Step> {
+        match &mut self.decoder {
+            Some(decoder) => {
+                let op = match decoder.get(self.cursor) {
+                    Some(op) => op,
+                    None => decoder.decode(&self.memory, self.cursor)?,
+                };
+                self.execute_decoded(op)
+            }
+            None => self.interpret(),
+        }
+    }
+
+    /// Decodes and executes the instruction at the cursor, restoring the cursor on errors
+    fn interpret(&mut self) -> Result<Step> {
+        let start = self.cursor;
+        self.last_write = None;
+        match self.execute_instruction() {
+            Ok(Step::Halted) => {
+                self.cursor = start;
+                Ok(Step::Halted)
+            }
+            Ok(_) => Ok(Step::Ran {
+                write: self.last_write,
+            }),
+            Err(err) => {
+                self.cursor = start;
+                Err(err)
+            }
+        }
+    }
+
+    fn execute_instruction(&mut self) -> Result<Step> {
+        match self.read_code()? {
+            1 => {
+                let v = self.read_value()? + self.read_value()?;
+                self.write_value(v)?;
+            }
+            2 => {
+                let v = self.read_value()? * self.read_value()?;
+                self.write_value(v)?;
+            }
+            3 => match self.input.pop_front() {
+                None => bail!("No input to read"),
+                Some(i) => {
+                    if let Err(err) = self.write_value(i) {
+                        self.input.push_front(i);
+                        return Err(err);
                     }
                 }
-                7 => {
-                    let v = self.read_value()? < self.read_value()?;
-                    self.write_bool(v)?;
+            },
+            4 => {
+                let v = self.read_value()?;
+                self.output.push(v)
+            }
+            5 => {
+                let jump = self.read_value()? != 0;
+                let p = self.read_value()?;
+                if jump {
+                    self.cursor = self.convert_to_cursor(p)?;
                 }
-                8 => {
-                    let v = self.read_value()? == self.read_value()?;
-                    self.write_bool(v)?;
+            }
+            6 => {
+                let jump = self.read_value()? == 0;
+                let p = self.read_value()?;
+                if jump {
+                    self.cursor = self.convert_to_cursor(p)?;
                 }
-                99 => return Ok(()),
-                code => bail!("Unexpected code {}", code),
             }
+            7 => {
+                let v = self.read_value()? < self.read_value()?;
+                self.write_bool(v)?;
+            }
+            8 => {
+                let v = self.read_value()? == self.read_value()?;
+                self.write_bool(v)?;
+            }
+            99 => return Ok(Step::Halted),
+            code => bail!("Unexpected code {}", code),
         }
+        Ok(Step::Ran { write: None })
     }
+
+    /// Executes an instruction from the decoder, with the same effects as `step`. The cursor
+    /// only moves once the instruction succeeded.
+    #[inline(always)]
+    fn execute_decoded(&mut self, op: Decoded) -> Result<Step> {
+        let get = |index: usize| match op.immediate[index] {
+            true => op.params[index],
+            false => self.memory[op.params[index] as usize],
+        };
+        let (jump, written) = match op.opcode {
+            Opcode::Add => (None, Some(get(0) + get(1))),
+            Opcode::Mul => (None, Some(get(0) * get(1))),
+            Opcode::In => match self.input.pop_front() {
+                None => bail!("No input to read"),
+                Some(i) => (None, Some(i)),
+            },
+            Opcode::Out => {
+                self.output.push(get(0));
+                (None, None)
+            }
+            Opcode::Jnz => ((get(0) != 0).then(|| get(1)), None),
+            Opcode::Jz => ((get(0) == 0).then(|| get(1)), None),
+            Opcode::Lt => (None, Some((get(0) < get(1)) as isize)),
+            Opcode::Eq => (None, Some((get(0) == get(1)) as isize)),
+            Opcode::Hlt => return Ok(Step::Halted),
+            Opcode::Arb => bail!("Unexpected code {}", Opcode::Arb as isize),
+        };
+        let mut write = None;
+        if let Some(value) = written {
+            // The written parameter is the last one, never immediate
+            let address = op.params[op.size as usize - 2] as usize;
+            write = Some((address, self.memory[address]));
+            self.write(address, value);
+        }
+        self.cursor = match jump {
+            Some(target) => check_address(&self.memory, target)?,
+            None => self.cursor + op.size as usize,
+        };
+        Ok(Step::Ran { write })
+    }
+}
+
+/// FNV-1a hash of the program values, stable across builds unlike the standard hasher so
+/// that saved snapshots stay valid
+fn hash_program(program: &[isize]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in program
+        .iter()
+        .flat_map(|value| (*value as i64).to_le_bytes())
+    {
+        hash = (hash ^ byte as u64).wrapping_mul(0x100000001b3);
+    }
+    hash
 }
 
 #[test]
-fn test_d05_example() -> Result<()> {
-    let _ = pretty_env_logger::try_init();
-    let program = concat!(
-        "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,",
-        "1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,",
-        "999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99"
+fn test_snapshots() -> Result<()> {
+    // Reads numbers and outputs their running sum, until a 0
+    let program = Input::from("3,12,1,12,13,13,4,13,1005,12,0,99,0,0");
+    let mut computer = Computer::new(&program)?;
+    computer.push_input(3);
+    assert_eq!(Status::NeedsInput, computer.run()?);
+    let snapshot = computer.snapshot();
+    assert_eq!((vec![3], 0), (snapshot.output.clone(), snapshot.cursor));
+
+    // Two alternatives from the same state
+    let mut fork = computer.fork();
+    fork.push_input(4);
+    assert_eq!(Status::NeedsInput, fork.run()?);
+    computer.push_input(10);
+    computer.push_input(0);
+    assert_eq!(Status::Halted, computer.run()?);
+    assert_eq!(
+        (vec![3, 7], vec![3, 13, 13]),
+        (fork.get_all_output(), computer.get_all_output())
     );
 
-    let mut computer = Computer::new(&Input::from(program))?;
-    let test_cases = vec![(7, 999), (8, 1000), (9, 1001)];
-    for (input, output) in test_cases {
-        computer.reset();
-        computer.push_input(input);
-        computer.execute()?;
-        assert_eq!(output, computer.pop_output()?)
-    }
+    computer.restore(&snapshot)?;
+    computer.push_input(0);
+    assert_eq!(Status::Halted, computer.run()?);
+    assert_eq!(vec![3, 3], computer.get_all_output());
+
+    // Resuming from a file
+    let path = std::env::temp_dir().join(format!("intcode-snapshot-{}.json", std::process::id()));
+    fork.snapshot().save(&path)?;
+    let loaded = Snapshot::load(&path)?;
+    std::fs::remove_file(&path)?;
+    assert_eq!(fork.snapshot(), loaded);
+    let mut resumed = Computer::new(&program)?;
+    resumed.restore(&loaded)?;
+    resumed.push_input(0);
+    assert_eq!(Status::Halted, resumed.run()?);
+    assert_eq!(vec![3, 7, 7], resumed.get_all_output());
 
+    let other = Computer::new(&Input::from("99"))?;
+    assert!(resumed.restore(&other.snapshot()).is_err());
+    // A program of the same size, whose memory would otherwise be accepted
+    let other = Computer::new(&Input::from("3,12,1,12,13,13,4,13,1005,12,0,99,0,1"))?;
+    assert!(resumed.restore(&other.snapshot()).is_err());
     Ok(())
 }
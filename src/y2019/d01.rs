@@ -1,24 +1,28 @@
 use crate::utils::Input;
 use anyhow::Result;
-use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(isize, isize)> {
-    let mut output: (isize, isize) = (0, 0);
+pub fn parse(input: &Input) -> Result<Vec<isize>> {
+    input.lines_into()
+}
 
-    for line in input.lines() {
-        let mass = isize::from_str(line)?;
-        let mut fuel = (mass / 3) - 2;
-        if fuel > 0 {
-            // Fuel needed for the module
-            output.0 += fuel;
+/// Fuel needed for the modules
+pub fn part1(masses: &[isize]) -> Result<isize> {
+    Ok(masses.iter().map(|mass| fuel(*mass)).sum())
+}
 
-            // Fuel needed for the fuel
-            while fuel > 0 {
-                output.1 += fuel;
-                fuel = (fuel / 3) - 2;
-            }
+/// Fuel needed for the modules, and for the fuel itself
+pub fn part2(masses: &[isize]) -> Result<isize> {
+    let mut total = 0;
+    for mass in masses {
+        let mut fuel = fuel(*mass);
+        while fuel > 0 {
+            total += fuel;
+            fuel = (fuel / 3) - 2;
         }
     }
+    Ok(total)
+}
 
-    Ok(output)
+fn fuel(mass: isize) -> isize {
+    ((mass / 3) - 2).max(0)
 }
@@ -2,21 +2,23 @@ use crate::utils::Input;
 use crate::y2019::computer::Computer;
 use anyhow::{bail, Result};
 
-pub fn run(input: &Input) -> Result<(isize, isize)> {
-    let mut output = (0, 0);
-    let mut computer = Computer::new(input)?;
+pub fn parse(input: &Input) -> Result<Computer> {
+    Computer::new(input)
+}
 
-    // Reproduce memory corruption
+/// Reproduce memory corruption
+pub fn part1(computer: &Computer) -> Result<isize> {
+    let mut computer = computer.clone();
     computer.alter_memory(1, 12);
     computer.alter_memory(2, 2);
     computer.execute()?;
-    output.0 = computer.read_memory(0);
-
-    // Solve for 19690720
-    let (noun, verb) = search_solution(computer)?;
-    output.1 = 100 * noun + verb;
+    Ok(computer.read_memory(0))
+}
 
-    Ok(output)
+/// Solve for 19690720
+pub fn part2(computer: &Computer) -> Result<isize> {
+    let (noun, verb) = search_solution(computer.clone())?;
+    Ok(100 * noun + verb)
 }
 
 fn search_solution(mut computer: Computer) -> Result<(isize, isize)> {
@@ -0,0 +1,10 @@
+use crate::utils::Input;
+use crate::y2019::computer::Computer;
+use anyhow::Result;
+
+/// Same search as `d02`, on a computer running pre-decoded instructions
+pub use crate::y2019::d02::{part1, part2};
+
+pub fn parse(input: &Input) -> Result<Computer> {
+    Ok(Computer::new(input)?.with_decoding())
+}
@@ -1,46 +1,78 @@
-use crate::utils::Input;
-use anyhow::Result;
+use crate::utils::{Direction, Input, Point2, Vector2};
+use anyhow::{bail, Result};
 use std::collections::HashMap;
-use std::str::{FromStr, Split};
+use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (usize::MAX, usize::MAX);
+pub fn parse(input: &Input) -> Result<(Segments, Segments)> {
     let mut lines = input.lines();
+    let (Some(one), Some(two)) = (lines.next(), lines.next()) else {
+        bail!("Expected two cables")
+    };
+    let one = Segments::from_str(one).map_err(|err| input.error(0, err))?;
+    let two = Segments::from_str(two).map_err(|err| input.error(1, err))?;
+    Ok((one, two))
+}
+
+/// Distance to the intersection closest to the central port
+pub fn part1(cables: &(Segments, Segments)) -> Result<usize> {
+    match intersections(cables)
+        .map(|(point, _)| point.manhattan(&Point2::ORIGIN))
+        .min()
+    {
+        None => bail!("No intersection found"),
+        Some(distance) => Ok(distance),
+    }
+}
+
+/// Fewest combined steps the cables must take to reach an intersection
+pub fn part2(cables: &(Segments, Segments)) -> Result<usize> {
+    match intersections(cables).map(|(_, steps)| steps).min() {
+        None => bail!("No intersection found"),
+        Some(steps) => Ok(steps),
+    }
+}
 
+/// Lists points where the cables intersect, with the total steps to get there
+fn intersections(cables: &(Segments, Segments)) -> impl Iterator<Item = (Point2, usize)> + '_ {
     // Parse path of first cable, deduplicate points by keeping the older occurrence
-    let segments_one = Segments(lines.next().unwrap().to_owned());
     let mut points_one = HashMap::new();
-    for (point, steps_one) in segments_one.points() {
+    for (point, steps_one) in cables.0.points() {
         points_one.entry(point).or_insert(steps_one);
     }
 
-    let segments_two = Segments(lines.next().unwrap().to_owned());
-    for (point, steps_two) in segments_two.points() {
-        if let Some(steps_one) = points_one.get(&point) {
-            output.0 = output.0.min(point.manhattan());
-            output.1 = output.1.min(steps_one + steps_two)
-        }
-    }
-    Ok(output)
+    cables.1.points().filter_map(move |(point, steps_two)| {
+        points_one
+            .get(&point)
+            .map(|steps_one| (point, steps_one + steps_two))
+    })
 }
 
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
-struct Point(isize, isize);
+/// Directions and lengths of the segments of a cable
+pub struct Segments(Vec<(Vector2, usize)>);
 
-impl Point {
-    fn manhattan(&self) -> usize {
-        self.0.unsigned_abs() + self.1.unsigned_abs()
+impl FromStr for Segments {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut segments = vec![];
+        for segment in s.split(',') {
+            let mut chars = segment.chars();
+            let Some(direction) = chars.next() else {
+                bail!("Empty segment")
+            };
+            let direction = Direction::try_from(direction)?.vector();
+            segments.push((direction, usize::from_str(chars.as_str())?));
+        }
+        Ok(Segments(segments))
     }
 }
 
-struct Segments(String);
-
 impl<'a> Segments {
     fn points(&'a self) -> PointParser<'a> {
         PointParser {
-            input: self.0.split(','),
-            position: Point(0, 0),
-            direction: Point(0, 0),
+            input: self.0.iter(),
+            position: Point2::ORIGIN,
+            direction: Vector2::ZERO,
             remaining_steps: 0,
             total_steps: 0,
         }
@@ -48,35 +80,27 @@ impl<'a> Segments {
 }
 
 struct PointParser<'a> {
-    input: Split<'a, char>,
-    position: Point,
-    direction: Point,
+    input: std::slice::Iter<'a, (Vector2, usize)>,
+    position: Point2,
+    direction: Vector2,
     remaining_steps: usize,
     total_steps: usize,
 }
 
 impl<'a> Iterator for PointParser<'a> {
-    type Item = (Point, usize); // Point and linear distance from origin
+    type Item = (Point2, usize); // Point and linear distance from origin
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.remaining_steps == 0 {
+        while self.remaining_steps == 0 {
             match self.input.next() {
                 None => return None,
-                Some(segment) => {
-                    let (dir, amount) = segment.split_at(1);
-                    self.direction = match dir {
-                        "U" => Point(0, 1),
-                        "D" => Point(0, -1),
-                        "R" => Point(1, 0),
-                        "L" => Point(-1, 0),
-                        _ => unimplemented!(),
-                    };
-                    self.remaining_steps = usize::from_str(amount).unwrap();
+                Some(&(direction, steps)) => {
+                    self.direction = direction;
+                    self.remaining_steps = steps;
                 }
             }
         }
-        self.position.0 += self.direction.0;
-        self.position.1 += self.direction.1;
+        self.position += self.direction;
         self.remaining_steps -= 1;
         self.total_steps += 1;
         Some((self.position, self.total_steps))
@@ -1,33 +1,24 @@
-use crate::utils::{CaptureParser, Input};
+use crate::scan;
+use crate::utils::Input;
 use anyhow::bail;
 use anyhow::Result;
-use lazy_static::lazy_static;
-use regex::Regex;
+use std::ops::RangeInclusive;
 
-lazy_static! {
-    static ref INPUT_RE: regex::Regex = Regex::new(r"^(\d{6})-(\d{6})$").unwrap();
+pub fn parse(input: &Input) -> Result<RangeInclusive<u32>> {
+    let (start, end) = scan!(input.all().trim_end(), "{u32}-{u32}")?;
+    if start < 100_000 || end > 999_999 {
+        bail!("Invalid range, expected six-digit numbers: {}", input.all())
+    }
+    Ok(start..=end)
 }
 
-pub fn run(input: &Input) -> Result<(u32, u32)> {
-    let mut output = (0, 0);
-
-    let range = match INPUT_RE.captures(input.all()) {
-        Some(captures) => captures.parse(1)?..=captures.parse(2)?,
-        _ => bail!("Invalid input: {}", input.all()),
-    };
-
-    // Brute-force all possible values in range, we could be smarter though
-    for number in range {
-        let valid = validate(number);
-        if valid.0 {
-            output.0 += 1;
-        }
-        if valid.1 {
-            output.1 += 1;
-        }
-    }
+// Brute-force all possible values in range, we could be smarter though
+pub fn part1(range: &RangeInclusive<u32>) -> Result<usize> {
+    Ok(range.clone().filter(|n| validate(*n).0).count())
+}
 
-    Ok(output)
+pub fn part2(range: &RangeInclusive<u32>) -> Result<usize> {
+    Ok(range.clone().filter(|n| validate(*n).1).count())
 }
 
 fn validate(number: u32) -> (bool, bool) {
@@ -2,8 +2,8 @@ use crate::utils::Input;
 use anyhow::{bail, Result};
 use std::collections::HashMap;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
+/// Maps each object to the center it orbits around
+pub fn parse(input: &Input) -> Result<HashMap<String, String>> {
     let mut orbits = HashMap::new();
     for line in input.lines() {
         let (center, object) = parse_names(line.to_owned());
@@ -11,19 +11,27 @@ pub fn run(input: &Input) -> Result<(usize, usize)> {
             bail!("Found duplicate entry")
         }
     }
+    Ok(orbits)
+}
 
-    // Part 1: count all direct and indirect orbital relations
+/// Count all direct and indirect orbital relations
+pub fn part1(orbits: &HashMap<String, String>) -> Result<usize> {
+    let mut count = 0;
     for object in orbits.keys() {
         let mut object = object;
         while let Some(parent) = orbits.get(object) {
             object = parent;
-            output.0 += 1;
+            count += 1;
         }
     }
+    Ok(count)
+}
 
-    // Part 2: count orbital jumps from YOU to SAN:
-    //  - There is only one way from YOU to the COM, build it
-    //  - Then find where it intersects with the SAN-COM path
+/// Count orbital jumps from YOU to SAN:
+///  - There is only one way from YOU to the COM, build it
+///  - Then find where it intersects with the SAN-COM path
+pub fn part2(orbits: &HashMap<String, String>) -> Result<usize> {
+    let mut jumps = 0;
     let mut object = "YOU";
     let mut your_parents = Vec::new();
     while let Some(parent) = orbits.get(object) {
@@ -33,14 +41,13 @@ pub fn run(input: &Input) -> Result<(usize, usize)> {
     object = "SAN";
     while let Some(parent) = orbits.get(object) {
         if let Some(pos) = your_parents.iter().position(|&r| r == parent) {
-            output.1 += pos;
+            jumps += pos;
             break;
         }
-        output.1 += 1;
+        jumps += 1;
         object = parent;
     }
-
-    Ok(output)
+    Ok(jumps)
 }
 
 fn parse_names(mut input: String) -> (String, String) {
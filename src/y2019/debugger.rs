@@ -0,0 +1,325 @@
+use crate::y2019::computer::{Computer, Step};
+use crate::y2019::intcode::{Instruction, Opcode};
+use anyhow::Result;
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt::{Display, Formatter};
+
+/// Number of executed instructions kept in the trace by default
+pub const DEFAULT_TRACE_SIZE: usize = 1000;
+
+/// Reason the execution stopped
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Stop {
+    /// A single instruction was executed
+    Stepped,
+    /// The cursor reached an address with a breakpoint
+    Breakpoint(usize),
+    /// The next instruction has an opcode with a breakpoint
+    Opcode(Opcode),
+    /// A watched address was written to
+    Watchpoint {
+        address: usize,
+        old: isize,
+        new: isize,
+    },
+    /// The condition given to `run_until` became true
+    Condition,
+    /// The maximum number of steps was executed
+    Limit,
+    Halted,
+}
+
+impl Display for Stop {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Stop::Stepped => write!(f, "stepped"),
+            Stop::Breakpoint(address) => write!(f, "breakpoint at {}", address),
+            Stop::Opcode(opcode) => write!(f, "breakpoint on {}", opcode.mnemonic()),
+            Stop::Watchpoint { address, old, new } => {
+                write!(f, "watchpoint on [{}]: {} -> {}", address, old, new)
+            }
+            Stop::Condition => write!(f, "condition met"),
+            Stop::Limit => write!(f, "step limit reached"),
+            Stop::Halted => write!(f, "halted"),
+        }
+    }
+}
+
+/// Executed instruction, as decoded just before its execution
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TraceEntry {
+    /// Number of instructions executed before this one
+    pub step: usize,
+    pub address: usize,
+    /// None for values that ran but do not decode to a valid instruction
+    pub instruction: Option<Instruction>,
+}
+
+impl Display for TraceEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "#{} {}: ", self.step, self.address)?;
+        match &self.instruction {
+            Some(instruction) => write!(f, "{}", instruction),
+            None => write!(f, "?"),
+        }
+    }
+}
+
+/// Runs a computer step by step, stopping on breakpoints and watchpoints and keeping a trace
+/// of the last executed instructions
+#[derive(Debug, Clone)]
+pub struct Debugger {
+    computer: Computer,
+    breakpoints: BTreeSet<usize>,
+    opcode_breakpoints: BTreeSet<isize>,
+    watchpoints: BTreeSet<usize>,
+    trace: VecDeque<TraceEntry>,
+    trace_size: usize,
+    steps: usize,
+    /// Cursor of the last stop on a breakpoint, ignored when resuming from there
+    stopped_at: Option<usize>,
+}
+
+impl Debugger {
+    pub fn new(computer: Computer) -> Self {
+        Self::with_trace_size(computer, DEFAULT_TRACE_SIZE)
+    }
+
+    /// Debugger keeping the last `trace_size` instructions in its trace
+    pub fn with_trace_size(computer: Computer, trace_size: usize) -> Self {
+        Self {
+            computer,
+            breakpoints: BTreeSet::new(),
+            opcode_breakpoints: BTreeSet::new(),
+            watchpoints: BTreeSet::new(),
+            trace: VecDeque::with_capacity(trace_size),
+            trace_size,
+            steps: 0,
+            stopped_at: None,
+        }
+    }
+
+    pub fn computer(&self) -> &Computer {
+        &self.computer
+    }
+
+    /// Gives access to the computer, for example to push inputs
+    pub fn computer_mut(&mut self) -> &mut Computer {
+        &mut self.computer
+    }
+
+    /// Resets the computer, clearing the trace but keeping breakpoints and watchpoints
+    pub fn reset(&mut self) {
+        self.computer.reset();
+        self.trace.clear();
+        self.steps = 0;
+        self.stopped_at = None;
+    }
+
+    /// Number of instructions executed since the start
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Last executed instructions, oldest first
+    pub fn trace(&self) -> impl Iterator<Item = &TraceEntry> {
+        self.trace.iter()
+    }
+
+    /// Instruction at the cursor, None if the values there do not decode
+    pub fn next_instruction(&self) -> Option<Instruction> {
+        Instruction::decode(self.computer.memory(), self.computer.cursor()).ok()
+    }
+
+    /// Stops before executing the instruction at an address. Returns false if there was
+    /// already one.
+    pub fn break_at(&mut self, address: usize) -> bool {
+        self.breakpoints.insert(address)
+    }
+
+    /// Stops before executing any instruction with an opcode
+    pub fn break_on(&mut self, opcode: Opcode) -> bool {
+        self.opcode_breakpoints.insert(opcode as isize)
+    }
+
+    /// Stops after any write to an address
+    pub fn watch(&mut self, address: usize) -> bool {
+        self.watchpoints.insert(address)
+    }
+
+    pub fn remove_breakpoint(&mut self, address: usize) -> bool {
+        self.breakpoints.remove(&address)
+    }
+
+    pub fn remove_opcode_breakpoint(&mut self, opcode: Opcode) -> bool {
+        self.opcode_breakpoints.remove(&(opcode as isize))
+    }
+
+    pub fn remove_watchpoint(&mut self, address: usize) -> bool {
+        self.watchpoints.remove(&address)
+    }
+
+    pub fn breakpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.breakpoints.iter().copied()
+    }
+
+    pub fn opcode_breakpoints(&self) -> impl Iterator<Item = Opcode> + '_ {
+        self.opcode_breakpoints
+            .iter()
+            .filter_map(|code| Opcode::from_code(*code))
+    }
+
+    pub fn watchpoints(&self) -> impl Iterator<Item = usize> + '_ {
+        self.watchpoints.iter().copied()
+    }
+
+    /// Executes a single instruction, ignoring breakpoints. Errors leave the computer on the
+    /// failed instruction.
+    pub fn step(&mut self) -> Result<Stop> {
+        self.stopped_at = None;
+        self.execute()
+    }
+
+    fn execute(&mut self) -> Result<Stop> {
+        let address = self.computer.cursor();
+        let instruction = self.next_instruction();
+        let write = match self.computer.step()? {
+            Step::Halted => return Ok(Stop::Halted),
+            Step::Ran { write } => write,
+        };
+        if self.trace.len() == self.trace_size {
+            self.trace.pop_front();
+        }
+        if self.trace_size > 0 {
+            self.trace.push_back(TraceEntry {
+                step: self.steps,
+                address,
+                instruction,
+            });
+        }
+        self.steps += 1;
+        match write.filter(|(address, _)| self.watchpoints.contains(address)) {
+            Some((address, old)) => Ok(Stop::Watchpoint {
+                address,
+                old,
+                new: self.computer.memory()[address],
+            }),
+            None => Ok(Stop::Stepped),
+        }
+    }
+
+    /// Runs until a breakpoint, a watchpoint, the halt or `max_steps` instructions. After a
+    /// stop on a breakpoint, running again resumes from it.
+    pub fn run(&mut self, max_steps: usize) -> Result<Stop> {
+        self.run_until(|_| false, max_steps)
+    }
+
+    /// Runs like `run`, also stopping once the condition is true after an instruction
+    pub fn run_until<F>(&mut self, mut condition: F, max_steps: usize) -> Result<Stop>
+    where
+        F: FnMut(&Computer) -> bool,
+    {
+        let resumed = self.stopped_at.take();
+        for count in 0..max_steps {
+            let cursor = self.computer.cursor();
+            if count > 0 || resumed != Some(cursor) {
+                if let Some(stop) = self.breakpoint() {
+                    self.stopped_at = Some(cursor);
+                    return Ok(stop);
+                }
+            }
+            let stop = self.execute()?;
+            if stop != Stop::Stepped {
+                return Ok(stop);
+            }
+            if condition(&self.computer) {
+                return Ok(Stop::Condition);
+            }
+        }
+        Ok(Stop::Limit)
+    }
+
+    /// Breakpoint matching the cursor or the instruction at the cursor
+    fn breakpoint(&self) -> Option<Stop> {
+        let cursor = self.computer.cursor();
+        if self.breakpoints.contains(&cursor) {
+            return Some(Stop::Breakpoint(cursor));
+        }
+        let code = self.computer.memory().get(cursor)?.rem_euclid(100);
+        match self.opcode_breakpoints.contains(&code) {
+            true => Opcode::from_code(code).map(Stop::Opcode),
+            false => None,
+        }
+    }
+}
+
+#[test]
+fn test_debugger() -> Result<()> {
+    use crate::utils::Input;
+
+    // Compares the input with 8, from the 2019 day 5 examples
+    let program = concat!(
+        "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,",
+        "1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,",
+        "999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99"
+    );
+    let mut debugger = Debugger::with_trace_size(Computer::new(&Input::from(program))?, 3);
+
+    // The input instruction fails without input, and can be retried
+    assert!(debugger.step().is_err());
+    assert_eq!(0, debugger.computer().cursor());
+    debugger.computer_mut().push_input(9);
+    assert_eq!(Stop::Stepped, debugger.step()?);
+    assert_eq!(2, debugger.computer().cursor());
+    assert_eq!(
+        "eq [21] #8 -> [20]",
+        debugger.next_instruction().unwrap().to_string()
+    );
+
+    debugger.watch(20);
+    assert_eq!(
+        Stop::Watchpoint {
+            address: 20,
+            old: 0,
+            new: 0
+        },
+        debugger.run(100)?
+    );
+    debugger.break_on(Opcode::Out);
+    let write = |old, new| Stop::Watchpoint {
+        address: 20,
+        old,
+        new,
+    };
+    assert_eq!(write(0, 1), debugger.run(100)?);
+    assert_eq!(write(1, 1001), debugger.run(100)?);
+    assert_eq!(Stop::Opcode(Opcode::Out), debugger.run(100)?);
+    assert_eq!(40, debugger.computer().cursor());
+
+    debugger.break_at(46);
+    assert_eq!(Stop::Breakpoint(46), debugger.run(100)?);
+    assert_eq!(vec![1001], debugger.computer().get_all_output());
+    assert_eq!(Stop::Halted, debugger.run(100)?);
+    assert_eq!(Stop::Halted, debugger.step()?);
+
+    let trace: Vec<String> = debugger.trace().map(TraceEntry::to_string).collect();
+    assert_eq!(
+        vec![
+            "#6 36: add #1000 #1 -> [20]",
+            "#7 40: out [20]",
+            "#8 42: jnz #1 #46"
+        ],
+        trace
+    );
+    assert_eq!(9, debugger.steps());
+
+    // Running until the first output
+    debugger.reset();
+    debugger.remove_opcode_breakpoint(Opcode::Out);
+    debugger.remove_watchpoint(20);
+    debugger.computer_mut().push_input(7);
+    let stop = debugger.run_until(|c| !c.get_all_output().is_empty(), 100)?;
+    assert_eq!((Stop::Condition, 33), (stop, debugger.computer().cursor()));
+    assert_eq!(Stop::Limit, debugger.run(1)?);
+    Ok(())
+}
@@ -0,0 +1,213 @@
+use crate::y2019::disassembler::disassemble;
+use crate::y2019::intcode::{Mode, Opcode};
+use anyhow::{bail, Result};
+use std::sync::Arc;
+
+/// Instruction ready to execute, without mode digits to decode. Addresses of position
+/// parameters are checked when decoding.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct Decoded {
+    pub opcode: Opcode,
+    /// Whether each parameter is an immediate value rather than an address
+    pub immediate: [bool; 3],
+    pub size: u8,
+    pub params: [isize; 3],
+}
+
+/// Instructions of a computer decoded once and cached by address, see
+/// `Computer::with_decoding`. Writes invalidate the cached instructions they overlap, so that
+/// self-modifying programs behave the same. Resets keep the instructions decoded from the
+/// program.
+#[derive(Debug, Clone)]
+pub(crate) struct DecodeCache {
+    /// Instructions found by disassembling the program, shared by the clones of the computer
+    decoded: Arc<[Option<Decoded>]>,
+    /// Decoded instructions, from the program or decoded again after writes
+    cache: Vec<Option<Decoded>>,
+    /// Whether the instruction in the cache matches the memory, cleared by overlapping writes.
+    /// Shifted by 3, a write at `address` invalidating `valid[address..address + 4]`.
+    valid: Vec<bool>,
+    /// Addresses decoded again since the last reset, whose instruction is not the program one,
+    /// flagged in `is_decoded_again` to list them once
+    decoded_again: Vec<usize>,
+    is_decoded_again: Vec<bool>,
+}
+
+impl DecodeCache {
+    pub fn new(program: &[isize]) -> Self {
+        let mut decoded = vec![None; program.len()];
+        for (address, instruction) in disassemble(program).code() {
+            // Relative mode is not supported, it fails when executed
+            if instruction.params.iter().all(|p| p.mode != Mode::Relative) {
+                decoded[*address] = decode(program, *address).ok();
+            }
+        }
+        let decoded: Arc<[Option<Decoded>]> = decoded.into();
+        Self {
+            cache: decoded.to_vec(),
+            valid: vec![true; program.len() + 3],
+            decoded_again: vec![],
+            is_decoded_again: vec![false; program.len()],
+            decoded,
+        }
+    }
+
+    /// Goes back to the instructions of the program, without allocating
+    pub fn reset(&mut self) {
+        self.valid.fill(true);
+        for &address in &self.decoded_again {
+            self.cache[address] = self.decoded[address];
+            self.is_decoded_again[address] = false;
+        }
+        self.decoded_again.clear();
+    }
+
+    /// Forgets the cached instructions overlapping a written address, which start at most 3
+    /// values before it
+    pub fn forget(&mut self, address: usize) {
+        self.valid[address..address + 4].copy_from_slice(&[false; 4]);
+    }
+
+    /// Forgets all cached instructions, after the whole memory was replaced
+    pub fn forget_all(&mut self) {
+        self.valid.fill(false);
+    }
+
+    /// Instruction at an address, None if it must be decoded again from the memory
+    #[inline]
+    pub fn get(&self, address: usize) -> Option<Decoded> {
+        match self.valid.get(address + 3) {
+            Some(true) => self.cache[address],
+            _ => None,
+        }
+    }
+
+    /// Decodes the instruction at an address of the memory and caches it
+    pub fn decode(&mut self, memory: &[isize], address: usize) -> Result<Decoded> {
+        if address >= memory.len() {
+            bail!("Cursor out of bounds: {}>={}", address, memory.len())
+        }
+        let op = decode(memory, address)?;
+        self.cache[address] = Some(op);
+        self.valid[address + 3] = true;
+        if !self.is_decoded_again[address] {
+            self.is_decoded_again[address] = true;
+            self.decoded_again.push(address);
+        }
+        Ok(op)
+    }
+}
+
+/// Decodes the instruction at an address of the memory, accepting the same encodings as
+/// `Computer`
+fn decode(memory: &[isize], address: usize) -> Result<Decoded> {
+    let value = memory[address];
+    let Some(opcode) = Opcode::from_code(value % 100).filter(|_| value >= 0) else {
+        bail!("Unexpected code {} at {}", value, address)
+    };
+    let mut decoded = Decoded {
+        opcode,
+        immediate: [false; 3],
+        size: 1 + opcode.params() as u8,
+        params: [0; 3],
+    };
+    let mut modes = value / 100;
+    for index in 0..opcode.params() {
+        let Some(&param) = memory.get(address + 1 + index) else {
+            bail!("Truncated instruction at {}", address)
+        };
+        decoded.params[index] = match modes % 10 {
+            0 => check_address(memory, param)? as isize,
+            1 if index < opcode.reads() => param,
+            1 => bail!("Attempted write in immediate mode at {}", address),
+            mode => bail!("Unexpected address mode {} at {}", mode, address),
+        };
+        decoded.immediate[index] = modes % 10 == 1;
+        modes /= 10;
+    }
+    Ok(decoded)
+}
+
+/// Checks an address read from memory
+pub(crate) fn check_address(memory: &[isize], p: isize) -> Result<usize> {
+    match usize::try_from(p) {
+        Ok(p) if p < memory.len() => Ok(p),
+        _ => bail!("Pointer out of bounds: {}", p),
+    }
+}
+
+#[test]
+fn test_decoded_computer() -> Result<()> {
+    use crate::utils::Input;
+    use crate::y2019::computer::{Computer, Status};
+
+    // The first addition turns the instruction at 4 into a multiplication
+    let program = Input::from("1,1,1,4,99,5,6,0,99");
+    let mut computer = Computer::new(&program)?.with_decoding();
+    for _ in 0..2 {
+        computer.execute()?;
+        assert_eq!(&[30, 1, 1, 4, 2, 5, 6, 0, 99], computer.memory());
+        computer.reset();
+        assert_eq!(99, computer.read_memory(4));
+    }
+
+    // Same outputs as the reference computer, on the comparisons of the 2019 day 5 examples
+    let program = Input::from(concat!(
+        "3,21,1008,21,8,20,1005,20,22,107,8,21,20,1006,20,31,",
+        "1106,0,36,98,0,0,1002,21,125,20,4,20,1105,1,46,104,",
+        "999,1105,1,46,1101,1000,1,20,4,20,1105,1,46,98,99"
+    ));
+    let mut reference = Computer::new(&program)?;
+    let mut computer = Computer::new(&program)?.with_decoding();
+    for input in 6..=10 {
+        reference.reset();
+        reference.push_input(input);
+        reference.execute()?;
+        computer.reset();
+        computer.push_input(input);
+        computer.execute()?;
+        assert_eq!(reference.get_all_output(), computer.get_all_output());
+    }
+
+    // The diagnostic program of day 5 modifies its own code
+    if let Ok(input) = Input::open("y2019", "d05") {
+        let mut reference = Computer::new(&input)?;
+        let mut computer = Computer::new(&input)?.with_decoding();
+        for input in [1, 5] {
+            reference.reset();
+            reference.push_input(input);
+            reference.execute()?;
+            computer.reset();
+            computer.push_input(input);
+            computer.execute()?;
+            assert_eq!(reference.get_all_output(), computer.get_all_output());
+        }
+    }
+
+    // Stepping reports the writes and stops on missing inputs like the reference computer
+    let program = Input::from("3,12,1,12,13,13,4,13,1005,12,0,99,0,0");
+    let mut reference = Computer::new(&program)?;
+    let mut computer = Computer::new(&program)?.with_decoding();
+    assert!(computer.step().is_err());
+    // Four instructions per input, then the halt
+    for (input, steps) in [(3, 4), (4, 4), (0, 5)] {
+        reference.push_input(input);
+        computer.push_input(input);
+        for _ in 0..steps {
+            assert_eq!(reference.step()?, computer.step()?);
+            assert_eq!(reference.cursor(), computer.cursor());
+        }
+    }
+    assert_eq!(Status::Halted, computer.run()?);
+    assert_eq!(vec![3, 7, 7], computer.get_all_output());
+
+    assert!(Computer::new(&Input::from("3,0,99"))?
+        .with_decoding()
+        .execute()
+        .is_err());
+    assert!(Computer::new(&Input::from("1,0,9,0,99"))?
+        .with_decoding()
+        .execute()
+        .is_err());
+    Ok(())
+}
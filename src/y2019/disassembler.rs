@@ -0,0 +1,171 @@
+use crate::y2019::intcode::{Instruction, Mode, Opcode};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+/// Values per line of data
+const DATA_WIDTH: usize = 8;
+
+/// Disassembled program: the instructions reachable from address 0, labels on jump targets,
+/// and the remaining values as data
+#[derive(Debug, Clone)]
+pub struct Listing {
+    program: Vec<isize>,
+    code: BTreeMap<usize, Instruction>,
+    labels: BTreeMap<usize, String>,
+}
+
+/// Follows the execution paths of a program from address 0. Jumps to addresses read from
+/// memory cannot be followed, the code they reach is shown as data.
+pub fn disassemble(program: &[isize]) -> Listing {
+    let mut code = BTreeMap::new();
+    let mut covered = vec![false; program.len()];
+    let mut pending = vec![0];
+    while let Some(address) = pending.pop() {
+        if code.contains_key(&address) {
+            continue;
+        }
+        let Ok(instruction) = Instruction::decode(program, address) else {
+            continue;
+        };
+        // Stop on code overlapping another instruction
+        let span = address..address + instruction.size();
+        if covered[span.clone()].iter().any(|c| *c) {
+            continue;
+        }
+        covered[span].iter_mut().for_each(|c| *c = true);
+
+        let condition = instruction
+            .params
+            .first()
+            .filter(|p| p.mode == Mode::Immediate);
+        let falls_through = match (instruction.opcode, condition.map(|p| p.value)) {
+            (Opcode::Hlt, _) => false,
+            (Opcode::Jnz, Some(value)) => value == 0,
+            (Opcode::Jz, Some(value)) => value != 0,
+            _ => true,
+        };
+        let jumps = match (instruction.opcode, condition.map(|p| p.value)) {
+            (Opcode::Jnz, Some(0)) => false,
+            (Opcode::Jz, Some(value)) => value == 0,
+            _ => true,
+        };
+        if falls_through {
+            pending.push(address + instruction.size());
+        }
+        if let Some(target) = instruction.jump_target().filter(|_| jumps) {
+            pending.push(target);
+        }
+        code.insert(address, instruction);
+    }
+
+    let labels = code
+        .values()
+        .filter_map(Instruction::jump_target)
+        .filter(|target| code.contains_key(target))
+        .map(|target| (target, format!("L{}", target)))
+        .collect();
+    Listing {
+        program: program.to_vec(),
+        code,
+        labels,
+    }
+}
+
+impl Listing {
+    /// Decoded instructions, by address
+    pub fn code(&self) -> &BTreeMap<usize, Instruction> {
+        &self.code
+    }
+
+    /// Labels of the jump targets, by address
+    pub fn labels(&self) -> &BTreeMap<usize, String> {
+        &self.labels
+    }
+
+    /// Shows an instruction, with labels instead of the addresses of jump targets
+    fn render(&self, instruction: &Instruction) -> String {
+        let label = instruction.jump_target().and_then(|t| self.labels.get(&t));
+        match label {
+            None => instruction.to_string(),
+            Some(label) => {
+                let condition = instruction.params[0];
+                format!("{} {} #{}", instruction.opcode.mnemonic(), condition, label)
+            }
+        }
+    }
+}
+
+/// Shows the program as assembly, each line commented with its address and raw values
+impl Display for Listing {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut address = 0;
+        while address < self.program.len() {
+            if let Some(label) = self.labels.get(&address) {
+                writeln!(f, "{}:", label)?;
+            }
+            let (text, len) = match self.code.get(&address) {
+                Some(instruction) => (self.render(instruction), instruction.size()),
+                None => {
+                    let len = (address..self.program.len())
+                        .take(DATA_WIDTH)
+                        .take_while(|a| *a == address || !self.code.contains_key(a))
+                        .count();
+                    let values = &self.program[address..address + len];
+                    let values: Vec<String> = values.iter().map(isize::to_string).collect();
+                    (format!("data {}", values.join(" ")), len)
+                }
+            };
+            let raw: Vec<String> = self.program[address..address + len]
+                .iter()
+                .map(isize::to_string)
+                .collect();
+            writeln!(f, "    {:<36} ; {}: {}", text, address, raw.join(","))?;
+            address += len;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_disassemble() {
+    // Compares the input with 8, from the 2019 day 5 examples
+    let program = [
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+    let listing = disassemble(&program);
+    let text = listing.to_string();
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|l| l.split(" ;").next().unwrap().trim())
+        .collect();
+    assert_eq!(
+        vec![
+            "in -> [21]",
+            "eq [21] #8 -> [20]",
+            "jnz [20] #L22",
+            "lt #8 [21] -> [20]",
+            "jz [20] #L31",
+            "jz #0 #L36",
+            "data 98 0 0",
+            "L22:",
+            "mul [21] #125 -> [20]",
+            "out [20]",
+            "jnz #1 #L46",
+            "L31:",
+            "out #999",
+            "jnz #1 #L46",
+            "L36:",
+            "add #1000 #1 -> [20]",
+            "out [20]",
+            "jnz #1 #L46",
+            "data 98",
+            "L46:",
+            "hlt",
+        ],
+        lines
+    );
+    assert!(text.contains("    in -> [21]                           ; 0: 3,21\n"));
+    assert_eq!(4, listing.labels().len());
+}
@@ -0,0 +1,242 @@
+use crate::utils::Input;
+use anyhow::{bail, Result};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// Parses a comma-separated Intcode program
+pub fn parse_program(input: &Input) -> Result<Vec<isize>> {
+    let mut program: Vec<isize> = Vec::new();
+    for value in input.all().trim().split(',') {
+        program.push(isize::from_str(value.trim())?);
+    }
+    Ok(program)
+}
+
+/// Formats a program as comma-separated values, like the inputs
+pub fn format_program(program: &[isize]) -> String {
+    let values: Vec<String> = program.iter().map(isize::to_string).collect();
+    values.join(",")
+}
+
+/// Intcode operation, with its numeric code as discriminant
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Opcode {
+    Add = 1,
+    Mul = 2,
+    In = 3,
+    Out = 4,
+    Jnz = 5,
+    Jz = 6,
+    Lt = 7,
+    Eq = 8,
+    Arb = 9,
+    Hlt = 99,
+}
+
+impl Opcode {
+    pub const ALL: [Opcode; 10] = [
+        Opcode::Add,
+        Opcode::Mul,
+        Opcode::In,
+        Opcode::Out,
+        Opcode::Jnz,
+        Opcode::Jz,
+        Opcode::Lt,
+        Opcode::Eq,
+        Opcode::Arb,
+        Opcode::Hlt,
+    ];
+
+    pub fn from_code(code: isize) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| *op as isize == code)
+    }
+
+    pub fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Add => "add",
+            Opcode::Mul => "mul",
+            Opcode::In => "in",
+            Opcode::Out => "out",
+            Opcode::Jnz => "jnz",
+            Opcode::Jz => "jz",
+            Opcode::Lt => "lt",
+            Opcode::Eq => "eq",
+            Opcode::Arb => "arb",
+            Opcode::Hlt => "hlt",
+        }
+    }
+
+    pub fn from_mnemonic(mnemonic: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|op| op.mnemonic() == mnemonic)
+    }
+
+    /// Number of parameters read by the operation
+    pub fn reads(self) -> usize {
+        match self {
+            Opcode::Add | Opcode::Mul | Opcode::Lt | Opcode::Eq => 2,
+            Opcode::Jnz | Opcode::Jz => 2,
+            Opcode::Out | Opcode::Arb => 1,
+            Opcode::In | Opcode::Hlt => 0,
+        }
+    }
+
+    /// Whether the operation writes its result into a last parameter
+    pub fn writes(self) -> bool {
+        matches!(
+            self,
+            Opcode::Add | Opcode::Mul | Opcode::In | Opcode::Lt | Opcode::Eq
+        )
+    }
+
+    pub fn params(self) -> usize {
+        self.reads() + self.writes() as usize
+    }
+
+    /// Whether the operation may jump to the address in its second parameter
+    pub fn is_jump(self) -> bool {
+        matches!(self, Opcode::Jnz | Opcode::Jz)
+    }
+}
+
+/// Addressing mode of a parameter
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Mode {
+    Position = 0,
+    Immediate = 1,
+    Relative = 2,
+}
+
+/// Parameter of an instruction, shown as `[12]` in position mode, `#12` in immediate mode and
+/// `[rb+12]` in relative mode
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Parameter {
+    pub mode: Mode,
+    pub value: isize,
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.mode {
+            Mode::Position => write!(f, "[{}]", self.value),
+            Mode::Immediate => write!(f, "#{}", self.value),
+            Mode::Relative => write!(f, "[rb{:+}]", self.value),
+        }
+    }
+}
+
+/// Decoded instruction, with one parameter per operand
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub params: Vec<Parameter>,
+}
+
+impl Instruction {
+    /// Decodes the instruction at an address. Encodings that do not round-trip, like unused
+    /// mode digits or immediate writes, are rejected as they are likely data.
+    pub fn decode(memory: &[isize], address: usize) -> Result<Self> {
+        let Some(&value) = memory.get(address) else {
+            bail!("address {} out of memory", address)
+        };
+        let Some(opcode) = Opcode::from_code(value.rem_euclid(100)).filter(|_| value >= 0) else {
+            bail!("invalid opcode {} at {}", value, address)
+        };
+        let mut modes = value / 100;
+        let mut params = Vec::with_capacity(opcode.params());
+        for index in 0..opcode.params() {
+            let mode = match modes % 10 {
+                0 => Mode::Position,
+                1 if index < opcode.reads() => Mode::Immediate,
+                2 => Mode::Relative,
+                mode => bail!(
+                    "invalid mode {} for parameter {} at {}",
+                    mode,
+                    index,
+                    address
+                ),
+            };
+            let Some(&value) = memory.get(address + 1 + index) else {
+                bail!("truncated instruction at {}", address)
+            };
+            params.push(Parameter { mode, value });
+            modes /= 10;
+        }
+        if modes != 0 {
+            bail!("unused parameter modes in {} at {}", value, address)
+        }
+        Ok(Self { opcode, params })
+    }
+
+    /// Encodes the instruction back to memory values
+    pub fn encode(&self) -> Vec<isize> {
+        let modes = self
+            .params
+            .iter()
+            .rev()
+            .fold(0, |modes, p| modes * 10 + p.mode as isize);
+        let mut values = vec![modes * 100 + self.opcode as isize];
+        values.extend(self.params.iter().map(|p| p.value));
+        values
+    }
+
+    /// Number of memory values of the instruction
+    pub fn size(&self) -> usize {
+        1 + self.params.len()
+    }
+
+    /// Parameters read by the instruction
+    pub fn inputs(&self) -> &[Parameter] {
+        &self.params[..self.opcode.reads()]
+    }
+
+    /// Parameter written by the instruction
+    pub fn output(&self) -> Option<&Parameter> {
+        self.params.get(self.opcode.reads())
+    }
+
+    /// Address of the jump, when it does not depend on memory
+    pub fn jump_target(&self) -> Option<usize> {
+        match self.params.get(1) {
+            Some(p) if self.opcode.is_jump() && p.mode == Mode::Immediate => {
+                usize::try_from(p.value).ok()
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Shows the instruction as assembly, like `add [12] #3 -> [7]`
+impl Display for Instruction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.opcode.mnemonic())?;
+        for param in self.inputs() {
+            write!(f, " {}", param)?;
+        }
+        if let Some(output) = self.output() {
+            write!(f, " -> {}", output)?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_instructions() -> Result<()> {
+    let memory = [1002, 12, 3, 7, 99, 21101, -1, 1, 5];
+    let mul = Instruction::decode(&memory, 0)?;
+    assert_eq!("mul [12] #3 -> [7]", mul.to_string());
+    assert_eq!(&memory[..4], mul.encode());
+    assert_eq!("hlt", Instruction::decode(&memory, 4)?.to_string());
+    let add = Instruction::decode(&memory, 5)?;
+    assert_eq!("add #-1 #1 -> [rb+5]", add.to_string());
+    assert_eq!(&memory[5..], add.encode());
+
+    assert!(Instruction::decode(&[11101, 1, 1, 1], 0).is_err());
+    assert!(Instruction::decode(&[101099], 0).is_err());
+    assert!(Instruction::decode(&[42], 0).is_err());
+    assert!(Instruction::decode(&[1, 1, 1], 0).is_err());
+    assert_eq!(
+        Some(9),
+        Instruction::decode(&[1105, 1, 9], 0)?.jump_target()
+    );
+    Ok(())
+}
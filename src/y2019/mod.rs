@@ -1,17 +1,23 @@
-mod computer;
+pub mod assembler;
+pub mod computer;
 pub mod d01;
 pub mod d02;
+pub mod d02_opt;
 pub mod d03;
 pub mod d04;
 pub mod d05;
 pub mod d06;
+pub mod debugger;
+mod decoded;
+pub mod disassembler;
+pub mod intcode;
 
-crate::generate_tests! {
+crate::register_solutions! {
     y2019,
-    d01: (3352674, 5026151),
-    d02: (4930687, 5335),
-    d03: (217, 3454),
-    d04: (530, 324),
-    d05: (4601506, 5525561),
-    d06: (301100, 547),
+    d01,
+    d02 | d02_opt,
+    d03,
+    d04,
+    d05,
+    d06,
 }
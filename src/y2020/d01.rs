@@ -3,12 +3,11 @@ use anyhow::{bail, Result};
 
 const TARGET_SUM: usize = 2020;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let numbers: Vec<usize> = input.lines_into()?;
-    Ok((compute_first(&numbers)?, compute_second(&numbers)?))
+pub fn parse(input: &Input) -> Result<Vec<usize>> {
+    input.lines_into()
 }
 
-fn compute_first(input: &[usize]) -> Result<usize> {
+pub fn part1(input: &[usize]) -> Result<usize> {
     for x in 0..input.len() {
         for y in x..input.len() {
             if input[x] + input[y] == TARGET_SUM {
@@ -19,7 +18,7 @@ fn compute_first(input: &[usize]) -> Result<usize> {
     bail!("No match")
 }
 
-fn compute_second(input: &[usize]) -> Result<usize> {
+pub fn part2(input: &[usize]) -> Result<usize> {
     for x in 0..input.len() {
         for y in x..input.len() {
             for z in y..input.len() {
@@ -1,30 +1,22 @@
-use crate::utils::{CaptureParser, Input};
-use anyhow::{bail, Result};
-use lazy_static::lazy_static;
-use regex::Regex;
+use crate::scan;
+use crate::utils::Input;
+use anyhow::Result;
 use std::str::FromStr;
 
-lazy_static! {
-    static ref INPUT_RE: regex::Regex = Regex::new(r"^(\d+)-(\d+) (\w): (\w+)$").unwrap();
+pub fn parse(input: &Input) -> Result<Vec<Entry>> {
+    input.lines_into()
 }
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
+pub fn part1(entries: &[Entry]) -> Result<usize> {
+    Ok(entries.iter().filter(|e| e.validate_first()).count())
+}
 
-    for line in input.lines() {
-        let entry = Entry::from_str(line)?;
-        if entry.validate_first() {
-            output.0 += 1;
-        }
-        if entry.validate_second() {
-            output.1 += 1;
-        }
-    }
-    Ok(output)
+pub fn part2(entries: &[Entry]) -> Result<usize> {
+    Ok(entries.iter().filter(|e| e.validate_second()).count())
 }
 
 /// Holds a parsed password entry and its validation params
-struct Entry {
+pub struct Entry {
     pos1: usize,
     pos2: usize,
     char: char,
@@ -35,15 +27,7 @@ impl FromStr for Entry {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match INPUT_RE.captures(s) {
-            None => bail!("Invalid input: {}", s),
-            Some(captures) => Ok(Entry {
-                pos1: captures.parse(1)?,
-                pos2: captures.parse(2)?,
-                char: captures.parse(3)?,
-                pass: captures.parse(4)?,
-            }),
-        }
+        scan!(s, "{usize}-{usize} {char}: {word}" => Entry { pos1, pos2, char, pass })
     }
 }
 
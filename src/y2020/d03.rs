@@ -1,85 +1,39 @@
-use crate::utils::Input;
+use crate::utils::{Grid, Input};
 use anyhow::{bail, Result};
 
 const TREE: char = '#';
 const OPEN: char = '.';
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 1);
-    let mut lines = input.lines();
-
-    // Read the first line to get the pattern width
-    let first_line = lines.next().expect("Empty input");
-    if !first_line.starts_with(OPEN) {
+/// Parses the terrain, the top left cell being the starting position
+pub fn parse(input: &Input) -> Result<Grid<char>> {
+    let terrain = Grid::parse(input, |c| match c {
+        TREE | OPEN => Ok(c),
+        _ => bail!("invalid terrain {}", c),
+    })?;
+    if terrain.get(0, 0) != Some(&OPEN) {
         bail!("Starting position is not open")
     }
-    let pattern_width = first_line.len();
-
-    // We'll read the input once and feed it progressively to several states
-    // First answer is the first tree counter, second is all counters multiplied
-    let mut counters = vec![
-        TreeCounter::new(pattern_width, 3, false),
-        TreeCounter::new(pattern_width, 1, false),
-        TreeCounter::new(pattern_width, 5, false),
-        TreeCounter::new(pattern_width, 7, false),
-        TreeCounter::new(pattern_width, 1, true),
-    ];
-
-    for line in lines {
-        // We could assume ASCII and handle [u8], but let's go the harder way
-        let terrain: Vec<char> = line.chars().collect();
-        if terrain.len() != pattern_width {
-            bail!("Inconsistent width")
-        }
-        for counter in &mut counters {
-            counter.ride(&terrain);
-        }
-    }
-
-    output.0 = counters[0].get_hits();
-    for counter in &counters {
-        output.1 *= counter.get_hits();
-    }
-
-    Ok(output)
+    Ok(terrain)
 }
 
-struct TreeCounter {
-    pattern_width: usize,
-    speed: usize,
-    skip_even: bool,
-    skipped: bool,
-    cursor: usize,
-    hit: usize,
+pub fn part1(terrain: &Grid<char>) -> Result<usize> {
+    Ok(count_trees(terrain, 3, 1))
 }
 
-impl TreeCounter {
-    fn new(pattern_width: usize, speed: usize, skip_even: bool) -> Self {
-        Self {
-            pattern_width,
-            speed,
-            skip_even,
-            skipped: false,
-            cursor: 0,
-            hit: 0,
-        }
-    }
-
-    fn ride(&mut self, terrain: &[char]) {
-        if self.skip_even {
-            self.skipped = !self.skipped;
-            if self.skipped {
-                return;
-            }
-        }
-
-        self.cursor = (self.cursor + self.speed) % self.pattern_width;
-        if terrain[self.cursor] == TREE {
-            self.hit += 1;
-        }
-    }
+pub fn part2(terrain: &Grid<char>) -> Result<usize> {
+    Ok([(3, 1), (1, 1), (5, 1), (7, 1), (1, 2)]
+        .iter()
+        .map(|(right, down)| count_trees(terrain, *right, *down))
+        .product())
+}
 
-    fn get_hits(&self) -> usize {
-        self.hit
-    }
+/// Counts the trees hit when going down the slope, the pattern repeating to the right
+fn count_trees(terrain: &Grid<char>, right: usize, down: usize) -> usize {
+    (0..terrain.height())
+        .step_by(down)
+        .enumerate()
+        .filter(|(step, y)| {
+            terrain.get_wrapping((step * right) as isize, *y as isize) == Some(&TREE)
+        })
+        .count()
 }
@@ -1,40 +1,38 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
-use lazy_static::lazy_static;
-use regex::{Match, Regex};
 use std::collections::BTreeSet;
 use std::str::FromStr;
 
-lazy_static! {
-    /// Regexp matching the seat description on the boarding pass
-    static ref SEAT_RE: regex::Regex = Regex::new(r"^([BF]{7})([RL]{3})$").unwrap();
-}
-
-pub fn run(input: &Input) -> Result<(u16, u16)> {
-    let mut output = (0, 0);
+/// Parses the boarding passes into a sorted set of seat IDs
+pub fn parse(input: &Input) -> Result<BTreeSet<u16>> {
     let mut known_ids = BTreeSet::new();
     for line in input.lines() {
-        let id = Seat::from_str(line)?.get_id();
-        // First result is the highest seat ID
-        output.0 = output.0.max(id);
-        // Store ID to deduce ours by elimination
-        known_ids.insert(id);
+        known_ids.insert(Seat::from_str(line)?.get_id());
     }
+    Ok(known_ids)
+}
 
-    // Our seat is between two known IDs, let's iterate over the sorted set to find a gap
+/// First result is the highest seat ID
+pub fn part1(known_ids: &BTreeSet<u16>) -> Result<u16> {
+    match known_ids.last() {
+        None => bail!("No seat found"),
+        Some(id) => Ok(*id),
+    }
+}
+
+/// Our seat is between two known IDs, let's iterate over the sorted set to find a gap
+pub fn part2(known_ids: &BTreeSet<u16>) -> Result<u16> {
     let mut prev_id = None;
     for id in known_ids {
         if let Some(prev_id) = prev_id {
             if id - prev_id == 2 {
                 // We found a gap in the set, let's sit here
-                output.1 = id - 1;
-                break;
+                return Ok(id - 1);
             }
         }
         prev_id.replace(id);
     }
-
-    Ok(output)
+    bail!("No free seat found")
 }
 
 struct Seat {
@@ -45,32 +43,30 @@ struct Seat {
 impl FromStr for Seat {
     type Err = anyhow::Error;
 
+    /// Reads seven `B` or `F` for the row, then three `R` or `L` for the column
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match SEAT_RE.captures(s) {
-            None => bail!("Invalid format: {}", s),
-            Some(captures) => Ok(Self {
-                row: Seat::parse_binary(captures.get(1), 'B')?,
-                column: Seat::parse_binary(captures.get(2), 'R')?,
+        match s.split_at_checked(7) {
+            Some((row, column)) if column.len() == 3 => Ok(Self {
+                row: Seat::parse_binary(row, 'B', 'F')?,
+                column: Seat::parse_binary(column, 'R', 'L')?,
             }),
+            _ => bail!("Invalid format: {}", s),
         }
     }
 }
 
 impl Seat {
-    fn parse_binary(input: Option<Match>, ones: char) -> Result<u8> {
-        match input {
-            None => bail!("Empty input"),
-            Some(input) => {
-                let mut result = 0;
-                for bit in input.as_str().chars() {
-                    result *= 2;
-                    if bit == ones {
-                        result += 1;
-                    }
-                }
-                Ok(result)
+    fn parse_binary(input: &str, ones: char, zeros: char) -> Result<u8> {
+        let mut result = 0;
+        for bit in input.chars() {
+            result *= 2;
+            match bit {
+                _ if bit == ones => result += 1,
+                _ if bit == zeros => {}
+                _ => bail!("Invalid character {} in {}", bit, input),
             }
         }
+        Ok(result)
     }
 
     // Max ID is 1023, u16 is safe
@@ -94,5 +90,8 @@ fn test_seat_parsing() -> Result<()> {
         assert_eq!(column, seat.column);
         assert_eq!(id, seat.get_id());
     }
+    assert!(Seat::from_str("BFFFBBFRR").is_err());
+    assert!(Seat::from_str("BFFFRBFRRR").is_err());
+    assert!(Seat::from_str("BFFFBBFRRRL").is_err());
     Ok(())
 }
@@ -1,16 +1,23 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
 
-pub fn run(input: &Input) -> Result<(u16, u16)> {
-    let mut min = u16::max_value();
-    let mut max = u16::min_value();
+pub fn parse(input: &Input) -> Result<Vec<u16>> {
+    input.lines_with(parse_seat_id)
+}
+
+pub fn part1(ids: &[u16]) -> Result<u16> {
+    Ok(ids.iter().copied().max().unwrap_or_default())
+}
+
+pub fn part2(ids: &[u16]) -> Result<u16> {
+    let mut min = u16::MAX;
+    let mut max = u16::MIN;
     let mut checksum = 0;
 
     // Iterate on all known tickets and XOR them in the checksum
-    for line in input.lines() {
-        let id = parse_seat_id(line)?;
-        min = min.min(id);
-        max = max.max(id);
+    for id in ids {
+        min = min.min(*id);
+        max = max.max(*id);
         checksum ^= id;
     }
 
@@ -20,7 +27,7 @@ pub fn run(input: &Input) -> Result<(u16, u16)> {
     }
 
     // The checksum value now only has one ticket ID XORed in it: ours
-    Ok((max, checksum))
+    Ok(checksum)
 }
 
 fn parse_seat_id(input: &str) -> Result<u16> {
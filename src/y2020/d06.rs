@@ -2,36 +2,30 @@ use crate::utils::Input;
 use anyhow::Result;
 use std::collections::HashSet;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-
-    // First part: anyone says yes
-    let mut anyone_yes: HashSet<char> = HashSet::new();
-    // Second part: everyone says yes
-    let mut individual_yes: Vec<HashSet<char>> = Vec::new();
-
+/// Parses the answers of each person, grouped by empty lines
+pub fn parse(input: &Input) -> Result<Vec<Vec<HashSet<char>>>> {
+    let mut groups = vec![vec![]];
     for line in input.lines() {
         if line.is_empty() {
-            // New group, increase counters and clear
-            output.0 += anyone_yes.len();
-            output.1 += count_everyone_yes(&individual_yes);
-            anyone_yes.clear();
-            individual_yes.clear();
-        } else {
-            let mut my_yes = HashSet::new();
-            for char in line.chars() {
-                anyone_yes.insert(char);
-                my_yes.insert(char);
-            }
-            individual_yes.push(my_yes);
+            groups.push(vec![]);
+        } else if let Some(group) = groups.last_mut() {
+            group.push(line.chars().collect());
         }
     }
+    Ok(groups)
+}
 
-    // Don't forget to account for the last group
-    output.0 += anyone_yes.len();
-    output.1 += count_everyone_yes(&individual_yes);
+/// First part: anyone says yes
+pub fn part1(groups: &[Vec<HashSet<char>>]) -> Result<usize> {
+    Ok(groups
+        .iter()
+        .map(|group| group.iter().flatten().collect::<HashSet<_>>().len())
+        .sum())
+}
 
-    Ok(output)
+/// Second part: everyone says yes
+pub fn part2(groups: &[Vec<HashSet<char>>]) -> Result<usize> {
+    Ok(groups.iter().map(|group| count_everyone_yes(group)).sum())
 }
 
 /// Fold individual answers for a group to only keep the intersection
@@ -2,33 +2,43 @@ use crate::utils::Input;
 use anyhow::{bail, Result};
 use bit_vec::BitVec;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-
-    // First part: anyone says yes
-    let mut anyone_yes = BitVec::from_elem(26, false);
-    // Second part: everyone says yes
-    let mut everyone_yes = BitVec::from_elem(26, true);
-
+/// Parses the answers of each person, grouped by empty lines
+pub fn parse(input: &Input) -> Result<Vec<Vec<BitVec>>> {
+    let mut groups = vec![vec![]];
     for line in input.lines() {
         if line.is_empty() {
-            // New group, increase counters and clear
-            output.0 += anyone_yes.count_true();
-            output.1 += everyone_yes.count_true();
-            anyone_yes.clear(); // All to false
-            everyone_yes.set_all(); // All to true
-        } else {
-            let answers = parse_answers(line)?;
-            anyone_yes.or(&answers);
-            everyone_yes.and(&answers);
+            groups.push(vec![]);
+        } else if let Some(group) = groups.last_mut() {
+            group.push(parse_answers(line)?);
         }
     }
+    Ok(groups)
+}
 
-    // Don't forget to account for the last group
-    output.0 += anyone_yes.count_true();
-    output.1 += everyone_yes.count_true();
+/// First part: anyone says yes
+pub fn part1(groups: &[Vec<BitVec>]) -> Result<usize> {
+    let mut count = 0;
+    for group in groups {
+        let mut anyone_yes = BitVec::from_elem(26, false);
+        for answers in group {
+            anyone_yes.or(answers);
+        }
+        count += anyone_yes.count_true();
+    }
+    Ok(count)
+}
 
-    Ok(output)
+/// Second part: everyone says yes
+pub fn part2(groups: &[Vec<BitVec>]) -> Result<usize> {
+    let mut count = 0;
+    for group in groups {
+        let mut everyone_yes = BitVec::from_elem(26, true);
+        for answers in group {
+            everyone_yes.and(answers);
+        }
+        count += everyone_yes.count_true();
+    }
+    Ok(count)
 }
 
 fn parse_answers(line: &str) -> Result<BitVec> {
@@ -1,39 +1,47 @@
-use crate::utils::{CaptureParser, Input};
+use crate::scan;
+use crate::utils::Input;
 use anyhow::{bail, Result};
-use lazy_static::lazy_static;
 use petgraph::graph::{DiGraph, NodeIndex};
 use petgraph::visit::EdgeRef;
 use petgraph::Direction::{Incoming, Outgoing};
-use regex::Regex;
 use std::collections::{HashMap, HashSet};
 
-lazy_static! {
-    /// Regexp matching part of a rule, group 2 holds the color of the bag
-    static ref RULE_RE: regex::Regex = Regex::new(r"((\d+)\s|^)(\w+ \w+) bag").unwrap();
-}
-pub fn run(input: &Input) -> Result<(usize, usize)> {
+pub fn parse(input: &Input) -> Result<Rules> {
     let mut rules: Rules = Default::default();
-    for line in input.lines() {
-        let mut matches = RULE_RE.captures_iter(line);
-        let container = match matches.next() {
-            None => bail!("Invalid input {}", line),
-            Some(m) => rules.get_node(m.try_get(3)?),
-        };
-        for m in matches {
-            let contained = rules.get_node(m.try_get(3)?);
-            rules.add_rule(container, contained, m.parse(2)?);
+    for (container, contents) in input.lines_with(parse_rule)? {
+        let container = rules.get_node(&container);
+        for (count, color) in contents {
+            let contained = rules.get_node(&color);
+            rules.add_rule(container, contained, count);
         }
     }
 
-    let golden = rules.get_node("shiny gold");
-    Ok((
-        rules.what_can_contain(golden).len(),
-        rules.count_nested_bags(golden),
-    ))
+    Ok(rules)
+}
+
+/// Reads the color of the container bag, with the count and color of each bag it contains
+fn parse_rule(line: &str) -> Result<(String, Vec<(u8, String)>)> {
+    let (container, contents): (String, String) = scan!(line, "{str} bags contain {str}.")?;
+    if contents == "no other bags" {
+        return Ok((container, vec![]));
+    }
+    let mut contained = vec![];
+    for bags in contents.split(", ") {
+        contained.push(scan!(bags.trim_end_matches('s'), "{u8} {str} bag")?);
+    }
+    Ok((container, contained))
+}
+
+pub fn part1(rules: &Rules) -> Result<usize> {
+    Ok(rules.what_can_contain(rules.find_node("shiny gold")?).len())
+}
+
+pub fn part2(rules: &Rules) -> Result<usize> {
+    Ok(rules.count_nested_bags(rules.find_node("shiny gold")?))
 }
 
 #[derive(Default)]
-struct Rules {
+pub struct Rules {
     pub colors: HashMap<String, NodeIndex<u32>>,
     pub rules: DiGraph<(), u8, u32>,
 }
@@ -50,6 +58,13 @@ impl Rules {
         }
     }
 
+    fn find_node(&self, name: &str) -> Result<NodeIndex<u32>> {
+        match self.colors.get(name) {
+            Some(idx) => Ok(*idx),
+            None => bail!("Unknown color {}", name),
+        }
+    }
+
     fn add_rule(&mut self, container: NodeIndex<u32>, contained: NodeIndex<u32>, count: u8) {
         self.rules.add_edge(container, contained, count);
     }
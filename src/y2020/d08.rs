@@ -2,21 +2,25 @@ use crate::utils::Input;
 use crate::y2020::computer::{CError, Computer, Instruction};
 use anyhow::{bail, Result};
 
-pub fn run(input: &Input) -> Result<(isize, isize)> {
-    let mut output = (0, 0);
-    let mut computer = Computer::new(input)?;
+pub fn parse(input: &Input) -> Result<Computer> {
+    Ok(Computer::new(input)?)
+}
 
-    // Part 1: run un-modified code
+/// Part 1: run un-modified code
+pub fn part1(computer: &Computer) -> Result<isize> {
+    let mut computer = computer.clone();
     match computer.run() {
         Err(CError::LoopDetected(_)) => {
             log::debug!("Execution ended with: {:?}", computer.run());
-            output.0 = computer.get_accumulator();
+            Ok(computer.get_accumulator())
         }
         other => bail!("Unexpected result: {:?}", other),
     }
+}
 
-    // Part 2: brute-force a patch to fix execution by changing either one JMP or one NOOP
-    computer.reset();
+/// Part 2: brute-force a patch to fix execution by changing either one JMP or one NOOP
+pub fn part2(computer: &Computer) -> Result<isize> {
+    let mut output = 0;
     for (pos, instruction) in computer.get_instructions().enumerate() {
         let mut cloned = computer.clone();
         match instruction {
@@ -26,7 +30,7 @@ pub fn run(input: &Input) -> Result<(isize, isize)> {
         }
         if let Ok(()) = cloned.run() {
             log::debug!("Success by patching position {}", pos);
-            output.1 = cloned.get_accumulator();
+            output = cloned.get_accumulator();
         }
     }
     Ok(output)
@@ -1,25 +1,32 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
-use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut past_values = vec![];
-    let mut invalid_value = 0;
+pub fn parse(input: &Input) -> Result<Vec<usize>> {
+    input.lines_into()
+}
+
+pub fn part1(values: &[usize]) -> Result<usize> {
+    Ok(values[find_invalid(values)?])
+}
+
+pub fn part2(values: &[usize]) -> Result<usize> {
+    let position = find_invalid(values)?;
+    find_weak(&values[..position], values[position])
+}
 
-    for line in input.lines() {
-        let value = usize::from_str(line)?;
-        if !is_valid(&past_values, value) {
+/// Returns the position of the first value that is not the sum of two previous ones
+fn find_invalid(values: &[usize]) -> Result<usize> {
+    for position in 0..values.len() {
+        if !is_valid(&values[..position], values[position]) {
             log::debug!(
                 "Found invalid value {} position {}",
-                value,
-                past_values.len()
+                values[position],
+                position
             );
-            invalid_value = value;
-            break;
+            return Ok(position);
         }
-        past_values.push(value)
     }
-    Ok((invalid_value, find_weak(&past_values, invalid_value)?))
+    bail!("No invalid value found")
 }
 
 /// Check that a given value is the sum of two of the last 25 values
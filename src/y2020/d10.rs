@@ -1,18 +1,25 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
+/// Parses the adapters' joltages, sorted and surrounded by the outlet and our device
+pub fn parse(input: &Input) -> Result<Vec<usize>> {
     // Outlet has a joltage of zero
     let mut joltages: Vec<usize> = input.lines_into()?;
     joltages.push(0);
     joltages.sort_unstable();
     // Add our device as highest adapter + 3
     joltages.push(joltages.last().unwrap() + 3);
+    Ok(joltages)
+}
 
-    let gaps = compute_gaps(&joltages)?;
+pub fn part1(joltages: &[usize]) -> Result<usize> {
+    let gaps = compute_gaps(joltages)?;
     log::debug!("Gap results: {:?} out of {} values", gaps, joltages.len());
+    Ok(gaps[0] * gaps[2])
+}
 
-    Ok((gaps[0] * gaps[2], compute_arrangements(&joltages)))
+pub fn part2(joltages: &[usize]) -> Result<usize> {
+    Ok(compute_arrangements(joltages))
 }
 
 /// Iterates over all joltages and returns number of gaps per size (1, 2 ,3)
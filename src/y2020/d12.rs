@@ -1,109 +1,76 @@
-use crate::utils::Input;
+use crate::utils::{Direction, Input, Point2, Vector2};
 use anyhow::{bail, Result};
 use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(isize, isize)> {
-    let mut ship1 = Ship1::new();
-    let mut ship2 = Ship2::new();
-    for line in input.lines() {
-        let instruction = Instruction::from_str(line)?;
-        ship1.execute(&instruction);
-        ship2.execute(&instruction);
+pub fn parse(input: &Input) -> Result<Vec<Instruction>> {
+    input.lines_into()
+}
+
+pub fn part1(instructions: &[Instruction]) -> Result<usize> {
+    let mut ship = Ship1::new();
+    for instruction in instructions {
+        ship.execute(instruction)?;
+    }
+    Ok(ship.position.manhattan(&Point2::ORIGIN))
+}
+
+pub fn part2(instructions: &[Instruction]) -> Result<usize> {
+    let mut ship = Ship2::new();
+    for instruction in instructions {
+        ship.execute(instruction)?;
     }
-    Ok((ship1.manhattan(), ship2.manhattan()))
+    Ok(ship.position.manhattan(&Point2::ORIGIN))
 }
 
 struct Ship1 {
-    north: isize,
-    east: isize,
+    position: Point2,
     direction: Direction,
 }
 
 impl Ship1 {
     fn new() -> Self {
         Self {
-            north: 0,
-            east: 0,
+            position: Point2::ORIGIN,
             direction: Direction::East,
         }
     }
 
-    fn execute(&mut self, instruction: &Instruction) {
-        match instruction {
-            Instruction::Move(Direction::North, amount) => self.north += amount,
-            Instruction::Move(Direction::South, amount) => self.north -= amount,
-            Instruction::Move(Direction::East, amount) => self.east += amount,
-            Instruction::Move(Direction::West, amount) => self.east -= amount,
-            Instruction::Forward(amount) => {
-                self.execute(&Instruction::Move(self.direction.clone(), *amount))
-            }
-            Instruction::Left(angle) => {
-                for _ in 0..angle / 90 {
-                    self.direction = self.direction.left();
-                }
-            }
-            Instruction::Right(angle) => {
-                for _ in 0..angle / 90 {
-                    self.direction = self.direction.right();
-                }
-            }
+    fn execute(&mut self, instruction: &Instruction) -> Result<()> {
+        match *instruction {
+            Instruction::Move(direction, amount) => self.position += direction.vector() * amount,
+            Instruction::Forward(amount) => self.position += self.direction.vector() * amount,
+            Instruction::Left(angle) => self.direction = self.direction.rotate(-angle)?,
+            Instruction::Right(angle) => self.direction = self.direction.rotate(angle)?,
         }
-    }
-
-    fn manhattan(&mut self) -> isize {
-        self.east.abs() + self.north.abs()
+        Ok(())
     }
 }
 
 struct Ship2 {
-    ship_north: isize, // Relative to start point
-    ship_east: isize,
-    waypoint_north: isize, // Relative to current ship position
-    waypoint_east: isize,
+    position: Point2,  // Relative to start point
+    waypoint: Vector2, // Relative to current ship position
 }
 
 impl Ship2 {
     fn new() -> Self {
         Self {
-            ship_north: 0,
-            ship_east: 0,
-            waypoint_north: 1,
-            waypoint_east: 10,
-        }
-    }
-    fn execute(&mut self, instruction: &Instruction) {
-        match instruction {
-            Instruction::Move(Direction::North, amount) => self.waypoint_north += amount,
-            Instruction::Move(Direction::South, amount) => self.waypoint_north -= amount,
-            Instruction::Move(Direction::East, amount) => self.waypoint_east += amount,
-            Instruction::Move(Direction::West, amount) => self.waypoint_east -= amount,
-            Instruction::Forward(amount) => {
-                self.ship_north += self.waypoint_north * amount;
-                self.ship_east += self.waypoint_east * amount;
-            }
-            Instruction::Left(angle) => {
-                for _ in 0..angle / 90 {
-                    let new_east = -self.waypoint_north;
-                    self.waypoint_north = self.waypoint_east;
-                    self.waypoint_east = new_east;
-                }
-            }
-            Instruction::Right(angle) => {
-                for _ in 0..angle / 90 {
-                    let new_east = self.waypoint_north;
-                    self.waypoint_north = -self.waypoint_east;
-                    self.waypoint_east = new_east;
-                }
-            }
+            position: Point2::ORIGIN,
+            waypoint: Vector2::new(10, -1),
         }
     }
 
-    fn manhattan(&mut self) -> isize {
-        self.ship_east.abs() + self.ship_north.abs()
+    fn execute(&mut self, instruction: &Instruction) -> Result<()> {
+        match *instruction {
+            Instruction::Move(direction, amount) => self.waypoint += direction.vector() * amount,
+            Instruction::Forward(amount) => self.position += self.waypoint * amount,
+            Instruction::Left(angle) => self.waypoint = self.waypoint.rotate(-angle)?,
+            Instruction::Right(angle) => self.waypoint = self.waypoint.rotate(angle)?,
+        }
+        Ok(())
     }
 }
 
-enum Instruction {
+pub enum Instruction {
     Move(Direction, isize),
     Forward(isize),
     Left(isize),
@@ -117,10 +84,7 @@ impl FromStr for Instruction {
         let (code, amount) = s.split_at(1);
         let amount = isize::from_str(amount)?;
         match code {
-            "N" => Ok(Instruction::Move(Direction::North, amount)),
-            "E" => Ok(Instruction::Move(Direction::East, amount)),
-            "S" => Ok(Instruction::Move(Direction::South, amount)),
-            "W" => Ok(Instruction::Move(Direction::West, amount)),
+            "N" | "E" | "S" | "W" => Ok(Instruction::Move(code.parse()?, amount)),
             "F" => Ok(Instruction::Forward(amount)),
             "L" => Ok(Instruction::Left(amount)),
             "R" => Ok(Instruction::Right(amount)),
@@ -128,31 +92,3 @@ impl FromStr for Instruction {
         }
     }
 }
-
-#[derive(Debug, Clone)]
-enum Direction {
-    North,
-    East,
-    South,
-    West,
-}
-
-impl Direction {
-    fn right(&self) -> Self {
-        match self {
-            Direction::North => Direction::East,
-            Direction::East => Direction::South,
-            Direction::South => Direction::West,
-            Direction::West => Direction::North,
-        }
-    }
-
-    fn left(&self) -> Self {
-        match &self {
-            Direction::North => Direction::West,
-            Direction::West => Direction::South,
-            Direction::South => Direction::East,
-            Direction::East => Direction::North,
-        }
-    }
-}
@@ -1,25 +1,40 @@
-use crate::utils::Input;
-use anyhow::Result;
+use crate::utils::{crt, Input};
+use anyhow::{bail, Result};
 use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
+/// Parses the departure time and the bus frequencies, None for out-of-service buses
+pub fn parse(input: &Input) -> Result<(usize, Vec<Option<usize>>)> {
     let mut lines = input.lines();
-    let departure: usize = lines.next().map(str::parse).unwrap().unwrap();
-    let busses = lines.next().unwrap();
+    let departure = match lines.next().map(usize::from_str) {
+        Some(Ok(departure)) => departure,
+        _ => bail!("Invalid departure time"),
+    };
+    let busses = match lines.next() {
+        None => bail!("Missing bus list"),
+        Some(busses) => busses.split(',').map(|b| usize::from_str(b).ok()).collect(),
+    };
+    Ok((departure, busses))
+}
 
+pub fn part1((departure, busses): &(usize, Vec<Option<usize>>)) -> Result<usize> {
     let mut earlier_id = 0;
     let mut earlier_time = usize::MAX;
-    for bus in busses.split(',') {
-        if let Ok(freq) = usize::from_str(bus) {
-            let next = freq - departure % freq;
-            if next < earlier_time {
-                earlier_time = next;
-                earlier_id = freq;
-            }
+    for freq in busses.iter().flatten() {
+        let next = freq - departure % freq;
+        if next < earlier_time {
+            earlier_time = next;
+            earlier_id = *freq;
         }
     }
-    output.0 = earlier_id * earlier_time;
+    Ok(earlier_id * earlier_time)
+}
 
-    Ok(output)
+/// Earliest timestamp where each bus departs as many minutes later as its position in the list
+pub fn part2((_, busses): &(usize, Vec<Option<usize>>)) -> Result<i64> {
+    let congruences: Vec<(i64, i64)> = busses
+        .iter()
+        .enumerate()
+        .filter_map(|(i, bus)| bus.map(|freq| (-(i as i64), freq as i64)))
+        .collect();
+    Ok(crt(&congruences)?.0)
 }
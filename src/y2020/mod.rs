@@ -15,19 +15,19 @@ pub mod d11;
 pub mod d12;
 pub mod d13;
 
-crate::generate_tests! {
+crate::register_solutions! {
     y2020,
-    d01: (866436, 276650720),
-    d02: (469, 267),
-    d03: (198, 5140884672),
-    d04: (254, 184),
-    d05: (871, 640), d05_opt: (871, 640),
-    d06: (6686, 3476), d06_opt: (6686, 3476),
-    d07: (177, 34988),
-    d08: (1930, 1688),
-    d09: (2089807806, 245848639),
-    d10: (2738, 74049191673856),
-    d11: (2261, 2039),
-    d12: (1177, 46530),
-    d13: (207, 0),
+    d01,
+    d02,
+    d03,
+    d04,
+    d05 | d05_opt,
+    d06 | d06_opt,
+    d07,
+    d08,
+    d09,
+    d10,
+    d11,
+    d12,
+    d13,
 }
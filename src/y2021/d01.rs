@@ -1,22 +1,21 @@
 use crate::utils::Input;
 use anyhow::Result;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let measurements: Vec<usize> = input.lines_into()?;
-    Ok((compute_first(&measurements), compute_second(&measurements)))
+pub fn parse(input: &Input) -> Result<Vec<usize>> {
+    input.lines_into()
 }
 
 // First part compares individual points
-fn compute_first(measurements: &[usize]) -> usize {
-    measurements
+pub fn part1(measurements: &[usize]) -> Result<usize> {
+    Ok(measurements
         .iter()
         .zip(measurements.iter().skip(1))
         .filter(|(prev, depth)| depth > prev)
-        .count()
+        .count())
 }
 
 // Second part compares sliding windows of three measurements
-fn compute_second(measurements: &[usize]) -> usize {
+pub fn part2(measurements: &[usize]) -> Result<usize> {
     let mut increases = 0;
     measurements.windows(3).fold(usize::MAX, |prev, win| {
         let sum = win.iter().sum();
@@ -25,5 +24,5 @@ fn compute_second(measurements: &[usize]) -> usize {
         }
         sum
     });
-    increases
+    Ok(increases)
 }
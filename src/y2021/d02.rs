@@ -1,32 +1,58 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
+use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let (mut position1, mut depth1) = (0_usize, 0_usize);
-    let (mut position2, mut depth2, mut aim2) = (0_usize, 0_usize, 0_usize);
+pub fn parse(input: &Input) -> Result<Vec<Command>> {
+    input.lines_into()
+}
+
+pub fn part1(commands: &[Command]) -> Result<usize> {
+    let (mut position, mut depth) = (0_usize, 0_usize);
+    for command in commands {
+        match command {
+            Command::Down(amount) => depth += amount,
+            Command::Up(amount) => depth -= amount,
+            Command::Forward(amount) => position += amount,
+        }
+    }
+    Ok(depth * position)
+}
+
+pub fn part2(commands: &[Command]) -> Result<usize> {
+    let (mut position, mut depth, mut aim) = (0_usize, 0_usize, 0_usize);
+    for command in commands {
+        match command {
+            Command::Down(amount) => aim += amount,
+            Command::Up(amount) => aim -= amount,
+            Command::Forward(amount) => {
+                position += amount;
+                depth += amount * aim;
+            }
+        }
+    }
+    Ok(depth * position)
+}
+
+pub enum Command {
+    Down(usize),
+    Up(usize),
+    Forward(usize),
+}
 
-    for line in input.lines() {
+impl FromStr for Command {
+    type Err = anyhow::Error;
+
+    fn from_str(line: &str) -> Result<Self> {
         let mut parts = line.split_whitespace();
         let amount: usize = match parts.next_back().map(str::parse) {
             Some(Ok(v)) => v,
             _ => bail!("invalid input {}", line),
         };
         match parts.next_back() {
-            Some("down") => {
-                depth1 += &amount;
-                aim2 += amount;
-            }
-            Some("up") => {
-                depth1 -= &amount;
-                aim2 -= amount;
-            }
-            Some("forward") => {
-                position1 += &amount;
-                position2 += &amount;
-                depth2 += amount * aim2;
-            }
+            Some("down") => Ok(Command::Down(amount)),
+            Some("up") => Ok(Command::Up(amount)),
+            Some("forward") => Ok(Command::Forward(amount)),
             _ => bail!("invalid input {}", line),
         }
     }
-    Ok((depth1 * position1, depth2 * position2))
 }
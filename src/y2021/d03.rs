@@ -4,16 +4,11 @@ use std::ops::{AddAssign, SubAssign};
 
 const REPORT_SIZE: usize = 12;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let values: Vec<u16> = input
-        .lines()
-        .map(|l| u16::from_str_radix(l, 2).unwrap())
-        .collect();
-
-    Ok((compute_first(&values)?, compute_second(&values)?))
+pub fn parse(input: &Input) -> Result<Vec<u16>> {
+    input.lines_with(|l| Ok(u16::from_str_radix(l, 2)?))
 }
 
-fn compute_first(values: &[u16]) -> Result<usize> {
+pub fn part1(values: &[u16]) -> Result<usize> {
     let mut seen: [i16; REPORT_SIZE] = [0; REPORT_SIZE];
     for v in values {
         for (i, s) in seen.iter_mut().enumerate() {
@@ -37,7 +32,7 @@ fn compute_first(values: &[u16]) -> Result<usize> {
     Ok(gamma * epsilon)
 }
 
-fn compute_second(values: &[u16]) -> Result<usize> {
+pub fn part2(values: &[u16]) -> Result<usize> {
     let o2 = find_value(values, REPORT_SIZE - 1, true)?;
     let co2 = find_value(values, REPORT_SIZE - 1, false)?;
     Ok(o2 * co2)
@@ -1,61 +1,95 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
 use std::ops::Add;
-use std::str::{FromStr, Lines};
+use std::str::FromStr;
 
 const ROW_SIZE: usize = 5;
 const ROW_COUNT: usize = 5;
 const MARK: u8 = u8::MAX;
 
-pub fn run(input: &Input) -> Result<(u16, u16)> {
-    let mut output = (0, 0);
-    let mut lines = input.lines();
-    let numbers: Vec<u8> = match lines.next() {
+/// Parses the drawn numbers and the bingo cards
+pub fn parse(input: &Input) -> Result<(Vec<u8>, Vec<Card>)> {
+    let mut blocks = input.blocks();
+    let numbers = match blocks.next() {
         None => bail!("empty input"),
-        Some(l) => l.split(',').map(|n| n.parse().unwrap()).collect(),
+        Some(line) => line
+            .split(',')
+            .map(u8::from_str)
+            .collect::<Result<_, _>>()?,
     };
+    let cards = blocks.map(Card::from_str).collect::<Result<_>>()?;
+    Ok((numbers, cards))
+}
+
+/// Score of the first card to win
+pub fn part1(game: &(Vec<u8>, Vec<Card>)) -> Result<u16> {
+    match play(game).first() {
+        None => bail!("no winning card"),
+        Some(score) => Ok(*score),
+    }
+}
 
-    let mut cards = Vec::with_capacity(100);
-    while let Some(card) = Card::parse(&mut lines) {
-        cards.push(card);
+/// Score of the last card to win
+pub fn part2(game: &(Vec<u8>, Vec<Card>)) -> Result<u16> {
+    match play(game).last() {
+        None => bail!("no winning card"),
+        Some(score) => Ok(*score),
     }
+}
 
+/// Plays the game and returns the scores of the cards, in the order they win
+fn play((numbers, cards): &(Vec<u8>, Vec<Card>)) -> Vec<u16> {
+    let mut cards = cards.clone();
+    let mut scores = Vec::with_capacity(cards.len());
     for number in numbers {
         cards.retain_mut(|card| {
-            if let Some(score) = card.process(number) {
-                if output.0 == 0 {
-                    output.0 = score;
-                }
-                output.1 = score;
+            if let Some(score) = card.process(*number) {
+                scores.push(score);
                 return false;
             }
             true
         });
     }
-
-    Ok(output)
+    scores
 }
 
-struct Card {
+#[derive(Clone)]
+pub struct Card {
     grid: [[u8; ROW_SIZE]; ROW_COUNT],
 }
 
-impl Card {
-    fn parse(lines: &mut Lines) -> Option<Self> {
-        lines.next()?; // End loop at EOF
+impl FromStr for Card {
+    type Err = anyhow::Error;
 
+    fn from_str(s: &str) -> Result<Self> {
         let mut grid: [[u8; ROW_SIZE]; ROW_COUNT] = [[0; ROW_SIZE]; ROW_COUNT];
+        let mut lines = s.lines();
         for row in grid.iter_mut() {
-            let mut numbers = lines
-                .next()
-                .unwrap()
+            let Some(line) = lines.next() else {
+                bail!("card with less than {} rows", ROW_COUNT)
+            };
+            let numbers: Vec<u8> = line
                 .split_whitespace()
-                .map(|n| u8::from_str(n).unwrap());
-            row.fill_with(|| numbers.next().unwrap());
+                .map(u8::from_str)
+                .collect::<Result<_, _>>()?;
+            if numbers.len() != ROW_SIZE {
+                bail!(
+                    "row with {} numbers instead of {}: {}",
+                    numbers.len(),
+                    ROW_SIZE,
+                    line
+                )
+            }
+            row.copy_from_slice(&numbers);
         }
-        Some(Self { grid })
+        if lines.next().is_some() {
+            bail!("card with more than {} rows", ROW_COUNT)
+        }
+        Ok(Self { grid })
     }
+}
 
+impl Card {
     fn process(&mut self, number: u8) -> Option<u16> {
         if let Some((i, j)) = self.mark(number) {
             if self.find_bingo(i, j) {
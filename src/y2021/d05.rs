@@ -1,128 +1,102 @@
-use crate::utils::Input;
-use anyhow::Result;
-use std::mem::swap;
-use std::ops::AddAssign;
+use crate::utils::{Grid, Input, Point2, Vector2};
+use anyhow::{bail, Result};
 use std::str::FromStr;
 
-const MAP_SIZE: usize = 1000;
-
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-    let mut map = Map::new();
-
-    let diagonals: Vec<Segment> = input
-        .lines()
-        .map(Segment::parse)
-        .filter(|s| match s {
-            Segment::Diagonal(_, _, _) => true,
-            _ => {
-                map.apply(s);
-                false
-            }
-        })
-        .collect();
-    output.0 = map.count_overlaps();
-
-    diagonals.iter().for_each(|s| map.apply(s));
-    output.1 = map.count_overlaps();
+pub fn parse(input: &Input) -> Result<Vec<Segment>> {
+    input.lines_into()
+}
 
-    Ok(output)
+/// Only consider horizontal and vertical lines
+pub fn part1(segments: &[Segment]) -> Result<usize> {
+    let mut map = Map::new(segments);
+    segments
+        .iter()
+        .filter(|s| !s.is_diagonal())
+        .for_each(|s| map.apply(s));
+    Ok(map.count_overlaps())
 }
 
-struct Point(usize, usize);
+pub fn part2(segments: &[Segment]) -> Result<usize> {
+    let mut map = Map::new(segments);
+    segments.iter().for_each(|s| map.apply(s));
+    Ok(map.count_overlaps())
+}
 
-enum Segment {
-    Horizontal(Point, usize),
-    Vertical(Point, usize),
-    Point(Point),
-    Diagonal(bool, Point, usize),
+/// Horizontal, vertical or 45° diagonal line
+pub struct Segment {
+    start: Point2,
+    end: Point2,
 }
 
 impl Segment {
-    fn parse(line: &str) -> Self {
-        let mut numbers = line
-            .split(|c: char| !c.is_ascii_digit())
-            .filter(|s| !s.is_empty())
-            .map(|s| usize::from_str(s).unwrap());
+    fn is_diagonal(&self) -> bool {
+        self.start.x() != self.end.x() && self.start.y() != self.end.y()
+    }
 
-        let mut start = Point(numbers.next().unwrap(), numbers.next().unwrap());
-        let mut end = Point(numbers.next().unwrap(), numbers.next().unwrap());
+    /// All points of the segment, including both ends
+    fn points(&self) -> impl Iterator<Item = Point2> {
+        let delta = self.end - self.start;
+        let step = Vector2::new(delta.x().signum(), delta.y().signum());
+        let start = self.start;
+        (0..=delta.chebyshev() as isize).map(move |i| start + step * i)
+    }
+}
 
-        if start.0 == end.0 {
-            if start.1 == end.1 {
-                return Segment::Point(start);
-            }
-            if start.1 > end.1 {
-                swap(&mut start, &mut end)
-            }
-            let length = end.1 - start.1 + 1;
-            return Segment::Horizontal(start, length);
-        }
+impl FromStr for Segment {
+    type Err = anyhow::Error;
 
-        if start.0 > end.0 {
-            swap(&mut start, &mut end)
-        }
-        if start.1 == end.1 {
-            let length = end.0 - start.0 + 1;
-            return Segment::Vertical(start, length);
-        }
-        if start.1 > end.1 {
-            let length = start.1 - end.1 + 1;
-            return Segment::Diagonal(false, start, length);
+    fn from_str(line: &str) -> Result<Self> {
+        let numbers: Vec<isize> = line
+            .split(|c: char| !c.is_ascii_digit())
+            .filter(|s| !s.is_empty())
+            .map(isize::from_str)
+            .collect::<Result<_, _>>()?;
+        let segment = match numbers[..] {
+            [x1, y1, x2, y2] => Segment {
+                start: Point2::new(x1, y1),
+                end: Point2::new(x2, y2),
+            },
+            _ => bail!("invalid segment {}", line),
+        };
+        let delta = segment.end - segment.start;
+        if segment.is_diagonal() && delta.x().abs() != delta.y().abs() {
+            bail!("segment {} is not at 45°", line)
         }
-        let length = end.1 - start.1 + 1;
-        Segment::Diagonal(true, start, length)
+        Ok(segment)
     }
 }
 
 struct Map {
-    grid: [[u8; MAP_SIZE]; MAP_SIZE],
+    grid: Grid<u8>,
     overlaps: usize,
 }
 
 impl Map {
-    fn new() -> Self {
+    /// Creates a map large enough for all segments
+    fn new(segments: &[Segment]) -> Self {
+        let (width, height) = segments
+            .iter()
+            .flat_map(|s| [s.start, s.end])
+            .fold((0, 0), |(w, h), p| {
+                (w.max(p.x() as usize + 1), h.max(p.y() as usize + 1))
+            });
         Self {
-            grid: [[0; MAP_SIZE]; MAP_SIZE],
+            grid: Grid::new(width, height, 0),
             overlaps: 0,
         }
     }
 
     fn apply(&mut self, s: &Segment) {
-        match s {
-            Segment::Point(pos) => self.mark(pos.0, pos.1),
-            Segment::Horizontal(orig, len) => {
-                for i in orig.1..orig.1 + len {
-                    self.mark(orig.0, i)
-                }
-            }
-            Segment::Vertical(orig, len) => {
-                for i in orig.0..orig.0 + len {
-                    self.mark(i, orig.1)
-                }
-            }
-            Segment::Diagonal(true, orig, len) => {
-                for i in 0..*len {
-                    self.mark(orig.0 + i, orig.1 + i)
-                }
-            }
-            Segment::Diagonal(false, orig, len) => {
-                for i in 0..*len {
-                    self.mark(orig.0 + i, orig.1 - i)
-                }
-            }
+        for point in s.points() {
+            self.mark(point)
         }
     }
 
-    fn mark(&mut self, x: usize, y: usize) {
-        let point = &mut self.grid[x][y];
-        match point {
-            0 => point.add_assign(1),
-            1 => {
-                point.add_assign(1);
-                self.overlaps.add_assign(1);
-            }
-            _ => {}
+    fn mark(&mut self, point: Point2) {
+        let count = &mut self.grid[(point.x() as usize, point.y() as usize)];
+        *count = count.saturating_add(1);
+        if *count == 2 {
+            self.overlaps += 1;
         }
     }
 
@@ -4,25 +4,29 @@ use std::collections::VecDeque;
 use std::ops::AddAssign;
 use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
+/// Counts the fishes per timer value
+pub fn parse(input: &Input) -> Result<VecDeque<usize>> {
     let mut generations = VecDeque::from([0_usize; 9]);
+    for number in input.all().trim().split(',') {
+        generations[usize::from_str(number)?] += 1;
+    }
+    Ok(generations)
+}
 
-    input.all().trim().split(',').for_each(|number| {
-        let number = usize::from_str(number).unwrap();
-        generations[number] += 1;
-    });
+pub fn part1(generations: &VecDeque<usize>) -> Result<usize> {
+    Ok(count_after(generations, 80))
+}
 
-    for _n in 0..80 {
-        simulate(&mut generations);
-    }
-    output.0 = generations.iter().sum();
+pub fn part2(generations: &VecDeque<usize>) -> Result<usize> {
+    Ok(count_after(generations, 256))
+}
 
-    for _n in 80..256 {
+fn count_after(generations: &VecDeque<usize>, days: usize) -> usize {
+    let mut generations = generations.clone();
+    for _n in 0..days {
         simulate(&mut generations);
     }
-    output.1 = generations.iter().sum();
-    Ok(output)
+    generations.iter().sum()
 }
 
 fn simulate(generations: &mut VecDeque<usize>) {
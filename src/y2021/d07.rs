@@ -2,27 +2,30 @@ use crate::utils::Input;
 use anyhow::Result;
 use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(i32, i32)> {
-    let positions: Vec<i32> = input
+pub fn parse(input: &Input) -> Result<Vec<i32>> {
+    Ok(input
         .all()
         .trim()
         .split(',')
         .map(|number| i32::from_str(number).unwrap())
-        .collect();
+        .collect())
+}
+
+pub fn part1(positions: &[i32]) -> Result<i32> {
+    Ok(minimize(|target| {
+        positions.iter().map(|pos| i32::abs(pos - target)).sum()
+    }))
+}
 
-    let mut minimum = (i32::MAX, i32::MAX);
-    for target in 0..1999_i32 {
-        let fuel1 = positions.iter().map(|pos| i32::abs(pos - target)).sum();
-        if fuel1 < minimum.0 {
-            minimum.0 = fuel1
-        }
-        let fuel2 = positions.iter().map(|pos| fuel2(pos, &target)).sum();
-        if fuel2 < minimum.1 {
-            minimum.1 = fuel2
-        }
-    }
+pub fn part2(positions: &[i32]) -> Result<i32> {
+    Ok(minimize(|target| {
+        positions.iter().map(|pos| fuel2(pos, &target)).sum()
+    }))
+}
 
-    Ok(minimum)
+/// Brute-force all target positions to find the lowest fuel cost
+fn minimize(cost: impl Fn(i32) -> i32) -> i32 {
+    (0..1999_i32).map(cost).min().unwrap_or(i32::MAX)
 }
 
 fn fuel2(pos: &i32, target: &i32) -> i32 {
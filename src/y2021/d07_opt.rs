@@ -2,8 +2,8 @@ use crate::utils::Input;
 use anyhow::Result;
 use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(i32, i32)> {
-    let mut output = (i32::MAX, i32::MAX);
+/// Parses the sorted crab positions
+pub fn parse(input: &Input) -> Result<Vec<i32>> {
     let mut positions: Vec<i32> = input
         .all()
         .trim()
@@ -11,18 +11,24 @@ pub fn run(input: &Input) -> Result<(i32, i32)> {
         .map(|number| i32::from_str(number).unwrap())
         .collect();
     positions.sort_unstable();
+    Ok(positions)
+}
 
-    // Part1: median minimizes the sum of offsets, it's the optimal position
-    let target1 = mean(&positions);
-    output.0 = positions.iter().map(|pos| i32::abs(pos - target1)).sum();
+/// Median minimizes the sum of offsets, it's the optimal position
+pub fn part1(positions: &[i32]) -> Result<i32> {
+    let target = mean(positions);
+    Ok(positions.iter().map(|pos| i32::abs(pos - target)).sum())
+}
 
-    // Part2 has one global optimum: search for it around the average position
+/// Part2 has one global optimum: search for it around the average position
+pub fn part2(positions: &[i32]) -> Result<i32> {
+    let mut minimum = i32::MAX;
     let avg = positions.iter().sum::<i32>() / positions.len() as i32;
     let mut descend_gradient = |target: i32| {
         let cost = positions.iter().map(|pos| fuel2(pos, target)).sum();
-        let down = cost < output.1;
+        let down = cost < minimum;
         if down {
-            output.1 = cost; // Store new minimum
+            minimum = cost; // Store new minimum
         }
         down
     };
@@ -39,7 +45,7 @@ pub fn run(input: &Input) -> Result<(i32, i32)> {
         }
     }
 
-    Ok(output)
+    Ok(minimum)
 }
 
 fn mean(values: &[i32]) -> i32 {
@@ -1,26 +1,33 @@
 use crate::utils::Input;
 use anyhow::Result;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-
-    for line in input.lines() {
-        let parts: Vec<&str> = line
-            .split(|c: char| !c.is_ascii_alphabetic())
-            .filter(|s| !s.is_empty())
-            .collect();
+/// Parses each line into its ten unique patterns, followed by the output digits
+pub fn parse(input: &Input) -> Result<Vec<Vec<String>>> {
+    Ok(input
+        .lines()
+        .map(|line| {
+            line.split(|c: char| !c.is_ascii_alphabetic())
+                .filter(|s| !s.is_empty())
+                .map(str::to_owned)
+                .collect()
+        })
+        .collect())
+}
 
-        parts.iter().skip(10).for_each(|s| match s.len() {
-            2 | 3 | 4 | 7 => output.0 += 1,
-            _ => {}
-        });
-        output.1 += decode_line(parts);
-    }
+/// Count output digits with a unique segment count
+pub fn part1(lines: &[Vec<String>]) -> Result<usize> {
+    Ok(lines
+        .iter()
+        .flat_map(|parts| parts.iter().skip(10))
+        .filter(|s| matches!(s.len(), 2 | 3 | 4 | 7))
+        .count())
+}
 
-    Ok(output)
+pub fn part2(lines: &[Vec<String>]) -> Result<usize> {
+    Ok(lines.iter().map(|parts| decode_line(parts)).sum())
 }
 
-fn decode_line(parts: Vec<&str>) -> usize {
+fn decode_line(parts: &[String]) -> usize {
     let mut decoder = Decoder::default();
     parts.iter().take(10).for_each(|p| decoder.train(p));
     decoder.finish_training();
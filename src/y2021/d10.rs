@@ -1,49 +1,69 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
+pub fn parse(input: &Input) -> Result<Vec<String>> {
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+/// Sum the scores of corrupted lines
+pub fn part1(lines: &[String]) -> Result<usize> {
+    let mut total = 0;
+    for line in lines {
+        if let Check::Corrupted(score) = check_line(line)? {
+            total += score;
+        }
+    }
+    Ok(total)
+}
+
+/// Middle score of the incomplete lines
+pub fn part2(lines: &[String]) -> Result<usize> {
     let mut valid_scores = vec![];
-    for line in input.lines() {
-        let mut open = vec![];
+    for line in lines {
+        if let Check::Incomplete(score) = check_line(line)? {
+            valid_scores.push(score);
+        }
+    }
+    if valid_scores.is_empty() {
+        bail!("no incomplete line")
+    }
+    valid_scores.sort_unstable();
+    Ok(valid_scores[valid_scores.len() / 2])
+}
 
-        let mut invalid_score = None;
-        for char in line.chars() {
-            match char {
-                '(' | '[' | '{' | '<' => open.push(char),
-                ')' | ']' | '}' | '>' => {
-                    let (opening, score) = match char {
-                        ')' => ('(', 3),
-                        ']' => ('[', 57),
-                        '}' => ('{', 1197),
-                        '>' => ('<', 25137),
-                        _ => bail!("unexpected value"),
-                    };
-                    if open.pop() != Some(opening) {
-                        invalid_score = Some(score);
-                        break;
-                    }
+enum Check {
+    Corrupted(usize),
+    Incomplete(usize),
+}
+
+fn check_line(line: &str) -> Result<Check> {
+    let mut open = vec![];
+    for char in line.chars() {
+        match char {
+            '(' | '[' | '{' | '<' => open.push(char),
+            ')' | ']' | '}' | '>' => {
+                let (opening, score) = match char {
+                    ')' => ('(', 3),
+                    ']' => ('[', 57),
+                    '}' => ('{', 1197),
+                    '>' => ('<', 25137),
+                    _ => bail!("unexpected value"),
+                };
+                if open.pop() != Some(opening) {
+                    return Ok(Check::Corrupted(score));
                 }
-                _ => {}
             }
+            _ => {}
         }
-        match invalid_score {
-            Some(score) => output.0 += score,
-            None => valid_scores.push(open.iter().rev().fold(0, |acc, c| {
-                let points = match c {
-                    '(' => 1,
-                    '[' => 2,
-                    '{' => 3,
-                    '<' => 4,
-                    _ => 0,
-                };
-                acc * 5 + points
-            })),
-        }
-        open.clear()
     }
-
-    valid_scores.sort_unstable();
-    output.1 = valid_scores[valid_scores.len() / 2];
-    Ok(output)
+    Ok(Check::Incomplete(open.iter().rev().fold(0, |acc, c| {
+        let points = match c {
+            '(' => 1,
+            '[' => 2,
+            '{' => 3,
+            '<' => 4,
+            _ => 0,
+        };
+        acc * 5 + points
+    })))
 }
@@ -1,64 +1,99 @@
-use crate::utils::Input;
-use anyhow::Result;
+use crate::utils::{Input, Search};
+use anyhow::{bail, Result};
 use petgraph::graph::{NodeIndex, UnGraph};
-use std::collections::{HashMap, VecDeque};
+use std::collections::HashMap;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
+/// Cave graph and its start node
+pub struct Caves {
+    graph: UnGraph<NodeType, (), u8>,
+    start: NodeIndex<u8>,
+}
+
+pub fn parse(input: &Input) -> Result<Caves> {
     let mut nodes: HashMap<String, NodeIndex<u8>> = HashMap::new();
     let mut graph: UnGraph<NodeType, (), u8> = UnGraph::default();
 
-    let mut get_node = |graph: &mut UnGraph<NodeType, (), u8>, name: &str| match nodes.get(name) {
-        Some(idx) => *idx,
-        None => {
-            let idx = graph.add_node(match name {
-                "start" => NodeType::Start,
-                "end" => NodeType::End,
-                _ => {
-                    if name.chars().next().unwrap().is_lowercase() {
-                        NodeType::SmallCave
-                    } else {
-                        NodeType::BigCave
-                    }
-                }
-            });
-            nodes.insert(name.to_string(), idx);
-            idx
+    let mut get_node = |graph: &mut UnGraph<NodeType, (), u8>, name: &str| {
+        if let Some(idx) = nodes.get(name) {
+            return Ok(*idx);
         }
+        let node_type = match name {
+            "start" => NodeType::Start,
+            "end" => NodeType::End,
+            _ => match name.chars().next() {
+                None => bail!("Empty cave name"),
+                Some(c) if c.is_lowercase() => NodeType::SmallCave,
+                Some(_) => NodeType::BigCave,
+            },
+        };
+        let idx = graph.add_node(node_type);
+        nodes.insert(name.to_string(), idx);
+        Ok(idx)
     };
-    for line in input.lines() {
+    for (index, line) in input.lines().enumerate() {
         if let Some((from, to)) = line.split_once('-') {
-            let from = get_node(&mut graph, from);
-            let to = get_node(&mut graph, to);
+            let mut node = |name| get_node(&mut graph, name).map_err(|err| input.error(index, err));
+            let from = node(from)?;
+            let to = node(to)?;
             graph.add_edge(from, to, ());
         }
     }
-    let mut queue = VecDeque::new();
-    queue.push_back(Path {
-        visited: Vec::with_capacity(16),
-        next: get_node(&mut graph, "start"),
-    });
+    let start = get_node(&mut graph, "start")?;
+    if graph.node_count() > 64 {
+        bail!("{} caves, at most 64 are supported", graph.node_count())
+    }
+    Ok(Caves { graph, start })
+}
+
+/// Enumerate all path without backtracking in small caves
+pub fn part1(caves: &Caves) -> Result<usize> {
+    caves.count_paths(false)
+}
 
-    // Part1: enumerate all path without backtracking in small caves
-    let (count1, mut backtracks) = traverse(&graph, &mut queue, true);
-    // Part2: consider additional path with one backtrack only
-    let (count2, _) = traverse(&graph, &mut backtracks, false);
-    Ok((count1, count1 + count2))
+/// Consider additional path with one backtrack only
+pub fn part2(caves: &Caves) -> Result<usize> {
+    caves.count_paths(true)
 }
 
-#[derive(Clone)]
+/// Position in the caves, with the set of small caves visited so far and whether a small cave
+/// was already visited twice
+#[derive(Clone, Copy, Eq, PartialEq, Hash)]
 struct Path {
-    visited: Vec<NodeIndex<u8>>,
-    next: NodeIndex<u8>,
+    cave: NodeIndex<u8>,
+    visited: u64,
+    backtracked: bool,
 }
 
-impl Path {
-    fn contains(&self, value: NodeIndex<u8>) -> bool {
-        self.next == value || self.visited.contains(&value)
-    }
-    fn copy_with(&self, next: NodeIndex<u8>) -> Self {
-        let mut visited = self.visited.clone();
-        visited.push(self.next);
-        Self { visited, next }
+impl Caves {
+    fn count_paths(&self, allow_backtrack: bool) -> Result<usize> {
+        let start = Path {
+            cave: self.start,
+            visited: 0,
+            backtracked: !allow_backtrack,
+        };
+        let successors = |path: &Path| {
+            let path = *path;
+            self.graph.neighbors(path.cave).filter_map(move |cave| {
+                let bit = 1 << cave.index();
+                match self.graph[cave] {
+                    NodeType::Start => None,
+                    NodeType::End | NodeType::BigCave => Some(Path { cave, ..path }),
+                    NodeType::SmallCave if path.visited & bit == 0 => Some(Path {
+                        cave,
+                        visited: path.visited | bit,
+                        ..path
+                    }),
+                    NodeType::SmallCave if !path.backtracked => Some(Path {
+                        cave,
+                        backtracked: true,
+                        ..path
+                    }),
+                    NodeType::SmallCave => None,
+                }
+            })
+        };
+        let is_end = |path: &Path| matches!(self.graph[path.cave], NodeType::End);
+        Search::new(start).count_paths(successors, is_end)
     }
 }
 
@@ -69,30 +104,7 @@ enum NodeType {
     SmallCave,
 }
 
-fn traverse(
-    graph: &UnGraph<NodeType, (), u8>,
-    queue: &mut VecDeque<Path>,
-    allow_backtracks: bool,
-) -> (usize, VecDeque<Path>) {
-    let mut count = 0;
-    let mut backtracks = VecDeque::new();
-    while let Some(path) = queue.pop_front() {
-        for next in graph.neighbors(path.next) {
-            match graph.node_weight(next).unwrap() {
-                NodeType::Start => {}
-                NodeType::End => count += 1,
-                NodeType::BigCave => queue.push_back(path.copy_with(next)),
-                NodeType::SmallCave => {
-                    if path.contains(next) {
-                        if allow_backtracks {
-                            backtracks.push_back(path.copy_with(next))
-                        }
-                    } else {
-                        queue.push_back(path.copy_with(next))
-                    }
-                }
-            }
-        }
-    }
-    (count, backtracks)
+#[test]
+fn test_empty_cave_name() {
+    assert!(parse(&Input::from("start-A\nA-\nA-end")).is_err());
 }
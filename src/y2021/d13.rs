@@ -1,30 +1,40 @@
-use crate::utils::Input;
+use crate::utils::{read_letters, Input};
 use anyhow::{bail, Result};
 use log::info;
 use std::collections::HashSet;
 use std::fmt::{Display, Formatter};
-use std::str::{FromStr, Lines};
+use std::str::FromStr;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-    let mut lines = input.lines();
-
-    let mut grid = Grid::parse(&mut lines)?;
+/// Parses the dots on the transparent paper, then the fold instructions
+pub fn parse(input: &Input) -> Result<(Grid, Vec<Fold>)> {
+    let (dots, folds) = input.sections()?;
+    let grid = Grid::parse(dots)?;
+    let folds = folds.lines().map(Fold::from_str).collect::<Result<_>>()?;
+    Ok((grid, folds))
+}
 
-    for (i, instruction) in lines.map(Fold::from_str).enumerate() {
-        grid.execute(instruction?)?;
-        if i == 0 {
-            output.0 = grid.len();
-        }
+/// Count dots after the first fold
+pub fn part1((grid, folds): &(Grid, Vec<Fold>)) -> Result<usize> {
+    let mut grid = grid.clone();
+    match folds.first() {
+        None => bail!("no fold instruction"),
+        Some(fold) => grid.execute(*fold)?,
     }
+    Ok(grid.len())
+}
 
-    output.1 = grid.len();
+/// Read the code after all folds
+pub fn part2((grid, folds): &(Grid, Vec<Fold>)) -> Result<String> {
+    let mut grid = grid.clone();
+    for fold in folds {
+        grid.execute(*fold)?;
+    }
     info!("The code is:\n\n{}", grid);
-    Ok(output)
+    read_letters(&grid.to_string())
 }
 
-#[derive(Debug)]
-enum Fold {
+#[derive(Debug, Clone, Copy)]
+pub enum Fold {
     X(u32),
     Y(u32),
 }
@@ -41,13 +51,17 @@ impl FromStr for Fold {
     }
 }
 
-struct Grid(HashSet<(u32, u32)>);
+#[derive(Clone)]
+pub struct Grid(HashSet<(u32, u32)>);
 
 impl Grid {
-    fn parse(input: &mut Lines) -> Result<Self> {
+    fn parse(input: &str) -> Result<Self> {
         let mut points = HashSet::with_capacity(908);
-        while let Some((x, y)) = input.next().and_then(|l| l.split_once(',')) {
-            points.insert((x.parse()?, y.parse()?));
+        for line in input.lines() {
+            match line.split_once(',') {
+                Some((x, y)) => points.insert((x.parse()?, y.parse()?)),
+                None => bail!("invalid dot {}", line),
+            };
         }
         Ok(Self(points))
     }
@@ -1,18 +1,14 @@
 use crate::utils::Input;
-use anyhow::Result;
+use anyhow::{bail, Result};
 use std::collections::HashMap;
 use std::mem::swap;
 use std::ops::AddAssign;
-use std::str::Lines;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-    let mut lines = input.lines();
-
-    // Read seed elements (first line), skip the empty line2, then parse rules
-    let seed = lines.next().unwrap();
-    assert_eq!(Some(""), lines.next());
-    let rules = Rules::parse(&mut lines)?;
+/// Parses the pair frequencies of the polymer template, and the insertion rules
+pub fn parse(input: &Input) -> Result<(Freq, Rules)> {
+    // Seed elements on the first line, then the rules after an empty line
+    let (seed, rules) = input.sections()?;
+    let rules = Rules::parse(rules)?;
 
     // Parse the seed into pairs, first char is in a (' ', c) pair for counts to be exact
     let mut prev = ' ';
@@ -23,16 +19,26 @@ pub fn run(input: &Input) -> Result<(usize, usize)> {
         prev = c;
     }
 
-    // Run the polymerisation and count occurrences
-    freqs.apply_rules(&rules, 10);
-    output.0 = freqs.freq_delta();
-    freqs.apply_rules(&rules, 30);
-    output.1 = freqs.freq_delta();
+    Ok((freqs, rules))
+}
 
-    Ok(output)
+pub fn part1(polymer: &(Freq, Rules)) -> Result<usize> {
+    Ok(polymerize(polymer, 10))
 }
 
-struct Freq(HashMap<(char, char), usize>);
+pub fn part2(polymer: &(Freq, Rules)) -> Result<usize> {
+    Ok(polymerize(polymer, 40))
+}
+
+/// Run the polymerisation and count occurrences
+fn polymerize((freqs, rules): &(Freq, Rules), iterations: usize) -> usize {
+    let mut freqs = freqs.clone();
+    freqs.apply_rules(rules, iterations);
+    freqs.freq_delta()
+}
+
+#[derive(Clone)]
+pub struct Freq(HashMap<(char, char), usize>);
 
 impl Freq {
     fn with_capacity(cap: usize) -> Self {
@@ -78,14 +84,16 @@ impl Freq {
 }
 
 type Pair = (char, char);
-struct Rules(HashMap<Pair, (Pair, Pair)>);
+pub struct Rules(HashMap<Pair, (Pair, Pair)>);
 
 impl Rules {
-    fn parse(input: &mut Lines) -> Result<Self> {
+    fn parse(input: &str) -> Result<Self> {
         let mut rules = HashMap::new();
-        for line in input {
+        for line in input.lines() {
             let chars: Vec<char> = line.chars().filter(|c| c.is_ascii_alphabetic()).collect();
-            assert_eq!(chars.len(), 3);
+            if chars.len() != 3 {
+                bail!("invalid rule {}", line)
+            }
             rules.insert(
                 (chars[0], chars[1]),
                 ((chars[0], chars[2]), (chars[2], chars[1])),
@@ -1,53 +1,46 @@
-use crate::utils::Input;
-use anyhow::Result;
+use crate::utils::{Answer, Grid, Input, Search};
+use anyhow::{bail, Context, Result};
 use log::debug;
-use petgraph::algo::dijkstra;
-use petgraph::{Directed, Graph};
 
-pub fn run(input: &Input) -> Result<(u32, usize)> {
-    let mut output = (0, 0);
-    let mut prev: Option<Vec<u32>> = None;
-    let mut graph: Graph<(), u32, Directed, usize> = Graph::default();
-
-    for (n, line) in input.lines().enumerate() {
-        let line: Vec<u32> = line.chars().map(|c| c as u32 - 48).collect();
-        if let Some(prev) = prev {
-            assert_eq!(prev.len(), line.len());
-            graph.extend_with_edges((0..line.len()).flat_map(|i| {
-                vec![
-                    (n * line.len() + i, (n - 1) * line.len() + i, prev[i]), // Going up
-                    ((n - 1) * line.len() + i, n * line.len() + i, line[i]), // Going down
-                ]
-            }));
-        };
-        graph.extend_with_edges(
-            line.iter()
-                .enumerate()
-                .zip(line.iter().enumerate().skip(1))
-                .flat_map(|((i1, v1), (i2, v2))| {
-                    vec![
-                        (n * line.len() + i1, n * line.len() + i2, *v2), // Going right
-                        (n * line.len() + i2, n * line.len() + i1, *v1), // Going left
-                    ]
-                }),
-        );
-        prev = Some(line);
+/// Risk level of each position of the cave, from 1 to 9
+pub fn parse(input: &Input) -> Result<Grid<u8>> {
+    let risks = Grid::parse(input, |c| match c.to_digit(10) {
+        Some(digit) if digit > 0 => Ok(digit as u8),
+        _ => bail!("invalid risk level {}", c),
+    })?;
+    if risks.width() == 0 || risks.height() == 0 {
+        bail!("empty cave")
     }
+    Ok(risks)
+}
 
-    let start = graph.node_indices().next().unwrap();
-    let end = graph.node_indices().next_back().unwrap();
-    let mut border_nodes = vec![];
-    for node in graph
-        .node_indices()
-        .filter(|&i| graph.neighbors(i).count() < 4)
-    {
-        border_nodes.push(node);
-    }
-    debug!("{:?} {:?} - {}", start, end, border_nodes.len());
+/// Lowest total risk from the top left to the bottom right position
+pub fn part1(risks: &Grid<u8>) -> Result<u32> {
+    let (width, height) = (risks.width(), risks.height());
+    let risk = |position: (usize, usize)| risks[position] as u32;
+    let end = (width - 1, height - 1);
+    let successors = |&(x, y): &(usize, usize)| {
+        let neighbours = [
+            x.checked_sub(1).map(|x| (x, y)),
+            (x + 1 < width).then_some((x + 1, y)),
+            y.checked_sub(1).map(|y| (x, y)),
+            (y + 1 < height).then_some((x, y + 1)),
+        ];
+        neighbours.into_iter().flatten().map(move |n| (n, risk(n)))
+    };
+    // Every position costs at least 1 to enter
+    let heuristic = |&(x, y): &(usize, usize)| (end.0 - x + end.1 - y) as u32;
+    let paths = Search::new((0, 0)).astar(successors, heuristic, |p| *p == end);
+    debug!("{} positions visited", paths.visited());
+    paths.goal_cost().context("no path to the bottom right")
+}
 
-    output.0 = *dijkstra(&graph, start, Some(end), |e| *e.weight())
-        .get(&end)
-        .unwrap();
+pub fn part2(_: &Grid<u8>) -> Result<Answer> {
+    Ok(Answer::Unsolved)
+}
 
-    Ok(output)
+#[test]
+fn test_invalid_cave() {
+    assert!(parse(&Input::from("19\n10")).is_err());
+    assert!(parse(&Input::from("")).is_err());
 }
@@ -2,17 +2,24 @@ use crate::utils::Input;
 use anyhow::{bail, Result};
 use std::collections::VecDeque;
 
-pub fn run(input: &Input) -> Result<(u32, i64)> {
+pub fn parse(input: &Input) -> Result<Packet> {
     let mut reader = BinaryReader::new(input.all().chars());
-    if let Some(packet) = reader.read_packet() {
-        Ok((packet.sum_versions(), packet.compute_value()))
-    } else {
-        bail!("invalid input")
+    match reader.read_packet() {
+        Some(packet) => Ok(packet),
+        None => bail!("invalid input"),
     }
 }
 
+pub fn part1(packet: &Packet) -> Result<u32> {
+    Ok(packet.sum_versions())
+}
+
+pub fn part2(packet: &Packet) -> Result<i64> {
+    Ok(packet.compute_value())
+}
+
 #[derive(Debug)]
-struct Packet {
+pub struct Packet {
     version: u32,
     kind: PacketKind,
     packets: Vec<Packet>,
@@ -174,37 +181,3 @@ where
         Some(packet)
     }
 }
-
-#[test]
-fn test_sum_versions() -> Result<()> {
-    let _ = pretty_env_logger::try_init();
-    for (input, output) in vec![
-        ("8A004A801A8002F478", 16),
-        ("620080001611562C8802118E34", 12),
-        ("C0015000016115A2E0802F182340", 23),
-        ("A0016C880162017C3686B18A3D4780", 31),
-    ] {
-        let mut b = BinaryReader::new(input.chars());
-        assert_eq!(output, b.read_packet().unwrap().sum_versions());
-    }
-    Ok(())
-}
-
-#[test]
-fn test_compute_value() -> Result<()> {
-    let _ = pretty_env_logger::try_init();
-    for (input, output) in vec![
-        ("C200B40A82", 3),
-        ("04005AC33890", 54),
-        ("880086C3E88112", 7),
-        ("CE00C43D881120", 9),
-        ("D8005AC2A8F0", 1),
-        ("F600BC2D8F", 0),
-        ("9C005AC2F8F0", 0),
-        ("9C0141080250320F1802104A08", 1),
-    ] {
-        let mut b = BinaryReader::new(input.chars());
-        assert_eq!(output, b.read_packet().unwrap().compute_value());
-    }
-    Ok(())
-}
@@ -1,22 +1,30 @@
-use crate::utils::{CaptureParser, Input};
+use crate::scan;
+use crate::utils::Input;
 use anyhow::{bail, Result};
-use lazy_static::lazy_static;
-use regex::Regex;
-
-lazy_static! {
-    static ref NUMBER_RE: regex::Regex = Regex::new(r"([-]?\d+)").unwrap();
-}
 
 const MAX_STEPS: u32 = 300;
 const SCAN_RANGE: i32 = 160;
 
-pub fn run(input: &Input) -> Result<(i32, usize)> {
-    let target = Target::parse(input.all())?;
+pub fn parse(input: &Input) -> Result<Target> {
+    Target::parse(input.all())
+}
+
+/// Highest position reached by a shot landing in the target
+pub fn part1(target: &Target) -> Result<i32> {
+    Ok(scan(target).0)
+}
 
+/// Count of initial velocities landing in the target
+pub fn part2(target: &Target) -> Result<usize> {
+    Ok(scan(target).1)
+}
+
+/// Shoots with all velocities in range, returns the highest position and count of hits
+fn scan(target: &Target) -> (i32, usize) {
     let (mut max_y, mut options) = (0, 0);
     for vx in 1..SCAN_RANGE {
         for vy in -SCAN_RANGE..SCAN_RANGE {
-            match shoot(&target, vx, vy) {
+            match shoot(target, vx, vy) {
                 (InTarget::Yes, max) => {
                     if max > max_y {
                         max_y = max;
@@ -30,7 +38,7 @@ pub fn run(input: &Input) -> Result<(i32, usize)> {
             }
         }
     }
-    Ok((max_y, options))
+    (max_y, options)
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -43,28 +51,19 @@ enum InTarget {
 }
 
 #[derive(Debug)]
-struct Target(i32, i32, i32, i32);
+pub struct Target(i32, i32, i32, i32);
 
 impl Target {
     fn parse(input: &str) -> Result<Self> {
-        let coordinates: Vec<i32> = NUMBER_RE
-            .captures_iter(input)
-            .map(|m| m.parse(1).unwrap())
-            .collect();
-        if coordinates.len() == 4 {
-            let target = Self(
-                coordinates[0],
-                coordinates[1],
-                coordinates[2],
-                coordinates[3],
-            );
-            if target.0 > target.1 || target.2 > target.3 {
-                bail!("invalid target coordinates: {:?}", target);
-            }
-            Ok(target)
-        } else {
-            bail!("invalid puzzle input: {}", input)
+        let (x1, x2, y1, y2) = scan!(
+            input.trim_end(),
+            "target area: x={i32}..{i32}, y={i32}..{i32}"
+        )?;
+        let target = Self(x1, x2, y1, y2);
+        if target.0 > target.1 || target.2 > target.3 {
+            bail!("invalid target coordinates: {:?}", target);
         }
+        Ok(target)
     }
 
     fn in_target(&self, x: i32, y: i32) -> InTarget {
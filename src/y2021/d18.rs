@@ -6,36 +6,36 @@ use std::iter::Peekable;
 use std::ops::AddAssign;
 use std::str::{Chars, FromStr};
 
-pub fn run(input: &Input) -> Result<(u32, u32)> {
-    let mut output = (0, 0);
-    output.0 = parse_and_sum(input.all().trim()).magnitude();
+pub fn parse(input: &Input) -> Result<Vec<Number>> {
+    input.lines_with(|line| match Number::parse(&mut line.chars().peekable()) {
+        Some(n) => Ok(n),
+        None => bail!("invalid line '{}'", line),
+    })
+}
 
-    let numbers: Vec<Number> =
-        input.lines_with(|line| match Number::parse(&mut line.chars().peekable()) {
-            Some(n) => Ok(n),
-            None => bail!("invalid line '{}'", line),
-        })?;
+/// Magnitude of the sum of all numbers
+pub fn part1(numbers: &[Number]) -> Result<u32> {
+    Ok(sum(numbers.to_vec()).magnitude())
+}
 
+/// Largest magnitude of the sum of two different numbers
+pub fn part2(numbers: &[Number]) -> Result<u32> {
+    let mut largest = 0;
     for left in numbers.iter() {
         for right in numbers.iter() {
             if left == right {
                 continue;
             }
 
-            output.1 = output
-                .1
-                .max(add_and_reduce(left.clone(), right.clone()).magnitude());
-            output.1 = output
-                .1
-                .max(add_and_reduce(right.clone(), left.clone()).magnitude());
+            largest = largest.max(add_and_reduce(left.clone(), right.clone()).magnitude());
+            largest = largest.max(add_and_reduce(right.clone(), left.clone()).magnitude());
         }
     }
-
-    Ok(output)
+    Ok(largest)
 }
 
 #[derive(Eq, PartialEq, Clone)]
-enum Number {
+pub enum Number {
     Pair(Box<Number>, Box<Number>),
     Literal(u32),
 }
@@ -96,7 +96,7 @@ impl Number {
     fn from_split(value: u32) -> Self {
         Number::Pair(
             Box::new(Literal(value / 2)),
-            if value % 2 == 0 {
+            if value.is_multiple_of(2) {
                 Box::new(Literal(value / 2))
             } else {
                 Box::new(Literal(value / 2 + 1))
@@ -144,7 +144,7 @@ impl Number {
                 match out {
                     Reduction::Continue => return Reduction::Continue,
                     Reduction::Split(v) => {
-                        *left = Box::new(Number::from_split(v));
+                        **left = Number::from_split(v);
                         return Reduction::Continue;
                     }
                     _ => {}
@@ -152,7 +152,7 @@ impl Number {
                 let out = right.split_one();
                 match out {
                     Reduction::Split(v) => {
-                        *right = Box::new(Number::from_split(v));
+                        **right = Number::from_split(v);
                         Reduction::Continue
                     }
                     _ => out,
@@ -182,7 +182,7 @@ impl Number {
                 Reduction::Break => {}
                 Reduction::Continue => return Reduction::Continue,
                 Reduction::Split(v) => {
-                    *left = Box::new(Number::from_split(v));
+                    **left = Number::from_split(v);
                     return Reduction::Continue;
                 }
                 Reduction::ExplodeLeft(v) => return Reduction::ExplodeLeft(v),
@@ -192,7 +192,7 @@ impl Number {
                 }
                 Reduction::Explode(l, r) => {
                     right.add_left(r);
-                    *left = Box::new(Number::Literal(0));
+                    **left = Number::Literal(0);
                     return Reduction::ExplodeLeft(l);
                 }
             }
@@ -202,7 +202,7 @@ impl Number {
                 Reduction::Break => Reduction::Break,
                 Reduction::Continue => Reduction::Continue,
                 Reduction::Split(v) => {
-                    *right = Box::new(Number::from_split(v));
+                    **right = Number::from_split(v);
                     Reduction::Continue
                 }
                 Reduction::ExplodeRight(v) => Reduction::ExplodeRight(v),
@@ -212,7 +212,7 @@ impl Number {
                 }
                 Reduction::Explode(l, r) => {
                     left.add_right(l);
-                    *right = Box::new(Number::Literal(0));
+                    **right = Number::Literal(0);
                     Reduction::ExplodeRight(r)
                 }
             }
@@ -228,8 +228,8 @@ fn add_and_reduce(left: Number, right: Number) -> Number {
     out
 }
 
-fn parse_and_sum(input: &str) -> Number {
-    let mut numbers = input.lines().map(|l| l.parse().unwrap());
+fn sum(numbers: Vec<Number>) -> Number {
+    let mut numbers = numbers.into_iter();
     let mut out = numbers.next().unwrap();
     for right in numbers {
         out = add_and_reduce(out, right)
@@ -240,7 +240,7 @@ fn parse_and_sum(input: &str) -> Number {
 #[test]
 fn test_explode_one() -> Result<()> {
     let _ = pretty_env_logger::try_init();
-    for (input, output) in vec![
+    for (input, output) in [
         // Explode
         ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
         ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
@@ -266,7 +266,7 @@ fn test_explode_one() -> Result<()> {
 #[test]
 fn test_split_one() -> Result<()> {
     let _ = pretty_env_logger::try_init();
-    for (input, output) in vec![("[10,0]", "[[5,5],0]"), ("[0,11]", "[0,[5,6]]")] {
+    for (input, output) in [("[10,0]", "[[5,5],0]"), ("[0,11]", "[0,[5,6]]")] {
         let mut number = Number::from_str(input).unwrap();
         number.split_one();
         assert_eq!(Number::from_str(output).unwrap(), number);
@@ -288,11 +288,11 @@ fn test_add() -> Result<()> {
 }
 
 #[test]
-fn test_parse_and_sum() -> Result<()> {
+fn test_sum() -> Result<()> {
     let _ = pretty_env_logger::try_init();
     let expected =
         Number::from_str("[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]")?;
-    let out = parse_and_sum(
+    let out = sum(parse(&Input::from(
         "[[[0,[5,8]],[[1,7],[9,6]]],[[4,[1,2]],[[1,4],2]]]
 [[[5,[2,8]],4],[5,[[9,9],0]]]
 [6,[[[6,2],[5,6]],[[7,6],[4,7]]]]
@@ -304,7 +304,7 @@ fn test_parse_and_sum() -> Result<()> {
 [[2,[[7,7],7]],[[5,8],[[9,3],[0,2]]]]
 [[[[5,2],5],[8,[3,7]]],[[5,[7,5]],[4,4]]]
 ",
-    );
+    ))?);
     assert_eq!(expected, out);
     assert_eq!(4140, out.magnitude());
     Ok(())
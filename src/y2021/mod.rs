@@ -18,25 +18,24 @@ pub mod d16;
 pub mod d17;
 pub mod d18;
 
-crate::generate_tests! {
+crate::register_solutions! {
     y2021,
-    d01: (1688, 1728),
-    d02: (1648020, 1759818555),
-    d03: (3923414, 5852595),
-    d04: (41503, 3178),
-    d05: (8622, 22037),
-    d06: (351188, 1595779846729),
-    d07: (348664, 100220525),
-    d07_opt: (348664, 100220525),
-    d08: (452, 1096964),
-    d09: (458, 1391940),
-    d10: (268845, 4038824534),
-    d11: (1647, 348),
-    d12: (3369, 85883),
-    d13: (763, 103),
-    d14: (4517, 4704817645083),
-    d15: (540, 0),
-    d16: (984, 1015320896946),
-    d17: (10585, 5247),
-    d18: (3734, 4837),
+    d01,
+    d02,
+    d03,
+    d04,
+    d05,
+    d06,
+    d07 | d07_opt,
+    d08,
+    d09,
+    d10,
+    d11,
+    d12,
+    d13,
+    d14,
+    d15,
+    d16,
+    d17,
+    d18,
 }
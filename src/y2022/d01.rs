@@ -2,11 +2,19 @@ use crate::utils::Input;
 use anyhow::Result;
 use std::cmp::Reverse;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
+/// Parses the calories carried by each elf, in decreasing order
+pub fn parse(input: &Input) -> Result<Vec<usize>> {
     let mut sums = parse_calories(input);
     sums.sort_unstable_by_key(|w| Reverse(*w));
+    Ok(sums)
+}
+
+pub fn part1(sums: &[usize]) -> Result<usize> {
+    Ok(sums.iter().take(1).sum())
+}
 
-    Ok((sums.iter().take(1).sum(), sums.iter().take(3).sum()))
+pub fn part2(sums: &[usize]) -> Result<usize> {
+    Ok(sums.iter().take(3).sum())
 }
 
 fn parse_calories(input: &Input) -> Vec<usize> {
@@ -100,9 +100,14 @@ fn compute_score2(line: &str) -> Result<usize> {
     }
 }
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    Ok((
-        input.lines_summed(compute_score1)?,
-        input.lines_summed(compute_score2)?,
-    ))
+pub fn parse(input: &Input) -> Result<Vec<String>> {
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+pub fn part1(rounds: &[String]) -> Result<usize> {
+    rounds.iter().map(|r| compute_score1(r)).sum()
+}
+
+pub fn part2(rounds: &[String]) -> Result<usize> {
+    rounds.iter().map(|r| compute_score2(r)).sum()
 }
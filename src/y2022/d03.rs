@@ -2,11 +2,15 @@ use crate::utils::Input;
 use anyhow::{bail, Result};
 use std::collections::HashSet;
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    Ok((input.lines_summed(compute_part1)?, compute_part2(input)?))
+pub fn parse(input: &Input) -> Result<Vec<String>> {
+    Ok(input.lines().map(str::to_owned).collect())
 }
 
-fn compute_part1(line: &str) -> Result<usize> {
+pub fn part1(sacks: &[String]) -> Result<usize> {
+    sacks.iter().map(|s| find_common_item(s)).sum()
+}
+
+fn find_common_item(line: &str) -> Result<usize> {
     let (left, right) = line.split_at(line.len() / 2);
     let left: HashSet<char> = HashSet::from_iter(left.chars());
 
@@ -18,9 +22,9 @@ fn compute_part1(line: &str) -> Result<usize> {
     bail!("no common item type found");
 }
 
-fn compute_part2(input: &Input) -> Result<usize> {
-    let result = input
-        .lines()
+pub fn part2(sacks: &[String]) -> Result<usize> {
+    let result = sacks
+        .iter()
         .map(|l| HashSet::from_iter(l.chars()))
         .fold(BadgeFolder::NoSack(0), BadgeFolder::fold);
     match result {
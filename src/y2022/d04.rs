@@ -1,46 +1,42 @@
-use crate::utils::{CaptureParser, Input};
-use anyhow::{bail, Result};
-use lazy_static::lazy_static;
-use regex::Regex;
+use crate::scan;
+use crate::utils::{Input, Interval};
+use anyhow::Result;
 use std::str::FromStr;
 
-lazy_static! {
-    static ref INPUT_RE: regex::Regex = Regex::new(r"^(\d+)-(\d+),(\d+)-(\d+)$").unwrap();
+pub fn parse(input: &Input) -> Result<Vec<AssignmentPair>> {
+    input.lines_into()
 }
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-    let assignments: Vec<AssignmentPair> = input.lines_into()?;
-    output.0 = assignments.iter().filter(|a| a.has_full_overlap()).count();
-    output.1 = assignments.iter().filter(|a| a.has_some_overlap()).count();
 
-    Ok(output)
+pub fn part1(assignments: &[AssignmentPair]) -> Result<usize> {
+    Ok(assignments.iter().filter(|a| a.has_full_overlap()).count())
 }
 
-struct AssignmentPair {
-    left: (usize, usize),
-    right: (usize, usize),
+pub fn part2(assignments: &[AssignmentPair]) -> Result<usize> {
+    Ok(assignments.iter().filter(|a| a.has_some_overlap()).count())
+}
+
+pub struct AssignmentPair {
+    left: Interval,
+    right: Interval,
 }
 
 impl FromStr for AssignmentPair {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match INPUT_RE.captures(s) {
-            Some(captures) => Ok(AssignmentPair {
-                left: (captures.parse(1)?, captures.parse(2)?),
-                right: (captures.parse(3)?, captures.parse(4)?),
-            }),
-            _ => bail!("invalid input shape"),
-        }
+        let (a, b, c, d) = scan!(s, "{i64}-{i64},{i64}-{i64}")?;
+        Ok(AssignmentPair {
+            left: Interval::inclusive(a, b),
+            right: Interval::inclusive(c, d),
+        })
     }
 }
 
 impl AssignmentPair {
     fn has_full_overlap(&self) -> bool {
-        self.left.0 >= self.right.0 && self.left.1 <= self.right.1
-            || self.left.0 <= self.right.0 && self.left.1 >= self.right.1
+        self.left.covers(&self.right) || self.right.covers(&self.left)
     }
     fn has_some_overlap(&self) -> bool {
-        self.left.0 <= self.right.1 && self.left.1 >= self.right.0
+        self.left.overlaps(&self.right)
     }
 }
@@ -1,8 +1,18 @@
 use crate::utils::Input;
 use anyhow::{bail, Result};
 
-pub fn run(input: &Input) -> Result<(usize, usize)> {
-    let mut output = (0, 0);
-    for line in input.lines() {}
+pub fn parse(input: &Input) -> Result<Vec<String>> {
+    Ok(input.lines().map(str::to_owned).collect())
+}
+
+pub fn part1(lines: &[String]) -> Result<usize> {
+    let mut output = 0;
+    for line in lines {}
+    Ok(output)
+}
+
+pub fn part2(lines: &[String]) -> Result<usize> {
+    let mut output = 0;
+    for line in lines {}
     Ok(output)
 }
@@ -4,10 +4,10 @@ pub mod d02;
 pub mod d03;
 pub mod d04;
 
-crate::generate_tests! {
+crate::register_solutions! {
     y2022,
-    d01: (67016, 200116),
-    d02: (8933, 11998),
-    d03: (8493, 2552),
-    d04: (528, 881),
+    d01,
+    d02,
+    d03,
+    d04,
 }